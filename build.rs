@@ -0,0 +1,143 @@
+/// Generates the solution registry used by the aggregate `all` binary.
+/// Every scaffolded `src/bin/NN.rs` and `src/bin/YYYY-NN.rs` is included as a module and its `SOLUTION` is registered.
+///
+/// Also generates a test module for every day from its example manifest, `data/examples/NN.json` or `data/YYYY/examples/NN.json`.
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+use tinyjson::JsonValue;
+
+fn main() -> io::Result<()> {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let bin_dir = manifest_dir.join("src/bin");
+
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?;
+            let is_digits =
+                |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+            // days of the default year are named `NN`, days of other years `YYYY-NN`.
+            let is_day = match stem.split_once('-') {
+                Some((year, day)) => is_digits(year, 4) && is_digits(day, 2),
+                None => is_digits(stem, 2),
+            };
+            (path.extension()? == "rs" && is_day)
+                .then(|| (stem.replace('-', "_"), path.to_str().unwrap().to_string()))
+        })
+        .collect();
+
+    days.sort_unstable();
+
+    let mut lines: Vec<String> = vec!["// @generated by build.rs, do not edit.".into()];
+
+    for (day, path) in &days {
+        lines.push(format!("#[path = {path:?}]"));
+        lines.push(format!("mod day_{day};"));
+    }
+
+    lines.push(String::new());
+    lines.push("/// Every puzzle that has been scaffolded in `src/bin`.".into());
+    lines.push(format!(
+        "static SOLUTIONS: &[advent_of_code::template::Solution] = &[{}];",
+        days.iter()
+            .map(|(day, _)| format!("day_{day}::SOLUTION"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+
+    fs::write(out_dir.join("solutions.rs"), lines.join("\n"))?;
+
+    write_example_tests(&manifest_dir, &out_dir.join("examples"), &days)
+}
+
+/// Writes `$OUT_DIR/examples/<bin>.rs` for every day, which `solution!` includes in test builds.
+/// Days without a manifest get an empty file.
+fn write_example_tests(
+    manifest_dir: &Path,
+    out_dir: &Path,
+    days: &[(String, String)],
+) -> io::Result<()> {
+    fs::create_dir_all(out_dir)?;
+
+    let data_dir = manifest_dir.join("data");
+    println!(
+        "cargo:rerun-if-changed={}",
+        data_dir.join("examples").display()
+    );
+
+    for (day, _) in days {
+        let bin = day.replace('_', "-");
+
+        // days of the default year are named `NN`, days of other years `YYYY-NN`.
+        let (examples_dir, day_number) = match bin.split_once('-') {
+            Some((year, day)) => (data_dir.join(year).join("examples"), day),
+            None => (data_dir.join("examples"), bin.as_str()),
+        };
+        println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+        let manifest_path = examples_dir.join(format!("{day_number}.json"));
+        let mut lines: Vec<String> = vec!["// @generated by build.rs, do not edit.".into()];
+
+        if let Ok(manifest) = fs::read_to_string(&manifest_path) {
+            let examples = parse_manifest(&manifest).unwrap_or_else(|e| {
+                panic!("invalid example manifest {}: {e}", manifest_path.display())
+            });
+
+            lines.push("mod example_tests {".into());
+            for (file, parts) in examples {
+                let name: String = file
+                    .trim_end_matches(".txt")
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect();
+
+                for part in parts {
+                    lines.push("    #[test]".into());
+                    lines.push(format!("    fn example_{name}_part_{part}() {{"));
+                    lines.push(format!("        super::__check_example({file:?}, {part});"));
+                    lines.push("    }".into());
+                }
+            }
+            lines.push("}".into());
+        }
+
+        fs::write(out_dir.join(format!("{bin}.rs")), lines.join("\n"))?;
+    }
+
+    Ok(())
+}
+
+/// Reads the example files of a manifest and the parts they have expected answers for.
+fn parse_manifest(manifest: &str) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let json: JsonValue = manifest.parse().map_err(|_| "not valid JSON file.")?;
+
+    json.get::<HashMap<String, JsonValue>>()
+        .and_then(|json| json.get("examples"))
+        .and_then(|examples| examples.get::<Vec<JsonValue>>())
+        .ok_or("expected `json.examples` to be an array.")?
+        .iter()
+        .map(|example| {
+            let example = example
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected example to be an object.")?;
+            let file = example
+                .get("file")
+                .and_then(|file| file.get::<String>())
+                .ok_or("expected example.file to be a string.")?;
+            let parts = [(1, "part_1"), (2, "part_2")]
+                .into_iter()
+                .filter(|(_, key)| example.contains_key(*key))
+                .map(|(part, _)| part)
+                .collect();
+            Ok((file.clone(), parts))
+        })
+        .collect()
+}
@@ -1,4 +1,4 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, part_two = [counting, naive]);
 
 use std::collections::{BinaryHeap};
 
@@ -23,24 +23,41 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(sum)
 }
 
-use std::collections::BTreeMap;
+pub mod part_two {
+    use std::collections::BTreeMap;
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut left = BTreeMap::new();
-    let mut right = BTreeMap::new();
-    for line in input.lines() {
-        let (l, r): (u32, u32) = {
-            let mut it = line.split_ascii_whitespace();
-            (it.next()?.parse().ok()?, it.next()?.parse().ok()?)
-        };
-        left.entry(l).and_modify(|v| *v += 1).or_insert(1);
-        right.entry(r).and_modify(|v| *v += 1).or_insert(1);
+    fn lists(input: &str) -> Option<(Vec<u32>, Vec<u32>)> {
+        input
+            .lines()
+            .map(|line| {
+                let mut it = line.split_ascii_whitespace();
+                Some((it.next()?.parse().ok()?, it.next()?.parse().ok()?))
+            })
+            .collect::<Option<Vec<(u32, u32)>>>()
+            .map(|pairs| pairs.into_iter().unzip())
+    }
+
+    pub fn counting(input: &str) -> Option<u32> {
+        let (left, right) = lists(input)?;
+        let mut counts = BTreeMap::new();
+        for r in right {
+            *counts.entry(r).or_insert(0) += 1;
+        }
+        let similarity = left
+            .into_iter()
+            .map(|n| counts.get(&n).unwrap_or(&0) * n)
+            .sum();
+        Some(similarity)
+    }
+
+    pub fn naive(input: &str) -> Option<u32> {
+        let (left, right) = lists(input)?;
+        let similarity = left
+            .iter()
+            .map(|&n| n * right.iter().filter(|&&r| r == n).count() as u32)
+            .sum();
+        Some(similarity)
     }
-    let similarity = left
-        .into_iter()
-        .map(|(n, c)| right.get(&n).unwrap_or(&0) * n * c)
-        .sum();
-    Some(similarity)
 }
 
 #[cfg(test)]
@@ -55,7 +72,8 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(part_two::counting(&input), Some(31));
+        assert_eq!(part_two::naive(&input), Some(31));
     }
 }
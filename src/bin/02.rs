@@ -43,20 +43,18 @@ fn is_safe_with_dampen(row: &[u32]) -> bool {
     true
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let safe_lines = input
-        .lines()
-        .map(|s| line.parse(s).unwrap())
-        .fold(0, |sum, row| sum + is_safe(&row) as u32);
-    Some(safe_lines)
+pub fn part_one(input: &str) -> Result<u32, String> {
+    input.lines().try_fold(0, |sum, s| {
+        let row = line.parse(s).map_err(|e| e.to_string())?;
+        Ok(sum + is_safe(&row) as u32)
+    })
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let safe_lines = input
-        .lines()
-        .map(|s| line.parse(s).unwrap())
-        .fold(0, |sum, row| sum + is_safe_with_dampen(&row) as u32);
-    Some(safe_lines)
+pub fn part_two(input: &str) -> Result<u32, String> {
+    input.lines().try_fold(0, |sum, s| {
+        let row = line.parse(s).map_err(|e| e.to_string())?;
+        Ok(sum + is_safe_with_dampen(&row) as u32)
+    })
 }
 
 #[cfg(test)]
@@ -66,12 +64,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        assert_eq!(result, Ok(2));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
+        assert_eq!(result, Ok(4));
     }
 }
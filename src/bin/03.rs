@@ -44,6 +44,7 @@ fn cmd(input: &mut &str) -> PResult<Cmd> {
     alt(((ex.map(Cmd::Exec)), mul.map(Cmd::Mul))).parse_next(input)
 }
 
+#[allow(dead_code)]
 fn mul_garbo(input: &mut &str) -> PResult<Mul> {
     let (v1,v2) = preceded(('m',opt(any),'u',opt(any),'l',opt(any)), delimited((opt(any),'(',opt(any)), separated_pair(dec_int,(opt(any),',',opt(any)),dec_int), (opt(any),')',opt(any)))).parse_next(input)?;
     Ok(Mul(v1,v2))
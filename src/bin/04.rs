@@ -87,6 +87,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(words.len() as u32)
 }
 
+#[allow(dead_code)]
 struct Cross(u32, u32);
 
 impl Cross {
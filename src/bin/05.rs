@@ -10,8 +10,6 @@ use winnow::{
     prelude::*,
 };
 
-use itertools::Itertools;
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 struct Rule(u32, u32);
 
@@ -41,6 +39,7 @@ impl Rule {
         }
     }
 
+    #[allow(dead_code)]
     fn fix(self, pages: &mut [u32]) {
         if let Some((first, second)) = self.find(pages) {
             if first > second {
@@ -49,14 +49,14 @@ fn problem(input: &mut &str) -> PResult<Problem> {
 
 advent_of_code::solution!(7);
 
-pub fn part_one(input: &str) -> Option<i64> {
+pub fn part_one(input: &str) -> Result<i64, String> {
     let mut results = 0;
     let problems: Vec<Problem> = input
         .lines()
-        .map(|line| problem.parse(line).unwrap())
-        .collect();
+        .map(|line| problem.parse(line).map_err(|e| e.to_string()))
+        .collect::<Result<_, _>>()?;
     for Problem { result, inputs } in problems {
-        let (start, rest) = inputs.split_first()?;
+        let (start, rest) = inputs.split_first().ok_or("problem without inputs")?;
         let operation_sequences =
             repeat_n([Operation::Add, Operation::Mul], rest.len()).multi_cartesian_product();
         for operations in operation_sequences {
@@ -70,17 +70,17 @@ pub fn part_one(input: &str) -> Option<i64> {
             }
         }
     }
-    Some(results)
+    Ok(results)
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
+pub fn part_two(input: &str) -> Result<i64, String> {
     let mut results = 0;
     let problems: Vec<Problem> = input
         .lines()
-        .map(|line| problem.parse(line).unwrap())
-        .collect();
+        .map(|line| problem.parse(line).map_err(|e| e.to_string()))
+        .collect::<Result<_, _>>()?;
     for Problem { result, inputs } in problems {
-        let (start, rest) = inputs.split_first()?;
+        let (start, rest) = inputs.split_first().ok_or("problem without inputs")?;
         let operation_sequences = repeat_n(
             [Operation::Add, Operation::Mul, Operation::Concat],
             rest.len(),
@@ -97,7 +97,7 @@ pub fn part_two(input: &str) -> Option<i64> {
             }
         }
     }
-    Some(results)
+    Ok(results)
 }
 
 #[cfg(test)]
@@ -121,12 +121,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
+        assert_eq!(result, Ok(3749));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
+        assert_eq!(result, Ok(11387));
     }
 }
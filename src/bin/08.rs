@@ -126,7 +126,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     for nodes in antennas.into_values() {
         for (p1, p2) in nodes.into_iter().tuple_combinations() {
             let antinodes = to_antinodes_part2(p1, p2);
-            unique_antinodes.extend(antinodes.into_iter());
+            unique_antinodes.extend(antinodes);
         }
     }
     Some(unique_antinodes.len() as u32)
@@ -10,7 +10,7 @@ use nalgebra::{Matrix2, Vector2};
 type Coord = Vector2<u64>;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
-struct Claw {
+pub struct Claw {
     a: Coord,
     b: Coord,
     prize: Coord,
@@ -78,16 +78,19 @@ fn claws_parser(input: &mut &str) -> PResult<Vec<Claw>> {
     separated(1.., claw_parser, multispace1).parse_next(input)
 }
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, try_parse = parse);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let claws = claws_parser.parse(input.trim()).ok()?;
+pub fn parse(input: &str) -> Option<Vec<Claw>> {
+    claws_parser.parse(input.trim()).ok()
+}
+
+pub fn part_one(claws: &[Claw]) -> Option<u64> {
     let cost = claws.iter().filter_map(|c| c.cost(false)).sum();
     Some(cost)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut claws = claws_parser.parse(input.trim()).ok()?;
+pub fn part_two(claws: &[Claw]) -> Option<u64> {
+    let mut claws = claws.to_vec();
     claws
         .iter_mut()
         .for_each(|claw| claw.prize.add_scalar_mut(10000000000000));
@@ -101,13 +104,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(480));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(875318608908));
     }
 }
@@ -52,6 +52,7 @@ impl Robot {
             v: (vx, vy),
         }
     }
+    #[allow(non_contiguous_range_endpoints)]
     const fn quadrant(&self) -> Option<Quadrant> {
         const Q1W: u32 = WIDTH / 2;
         const Q2W: u32 = Q1W + 1;
@@ -33,6 +33,7 @@ impl Tile {
             _ => None,
         }
     }
+    #[allow(dead_code)]
     const fn to_c(self) -> char {
         match self {
             Tile::Wall => '#',
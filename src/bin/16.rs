@@ -88,6 +88,7 @@ enum Turn {
 }
 
 impl Turn {
+    #[allow(dead_code)]
     const fn weight(self) -> u32 {
         match self {
             Turn::Left | Turn::Right => 1001,
@@ -175,7 +176,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     let maze: Vec<_> = input.lines().map(str::as_bytes).collect();
     let deer = find_start(&maze)?;
     let end = find_end(&maze)?;
-    let (paths, score) = astar_bag_collect(
+    let (paths, _) = astar_bag_collect(
         &deer,
         |nd| options(&maze, nd),
         |nd| nd.dist_to(end),
@@ -2,7 +2,6 @@ advent_of_code::solution!(18);
 
 use std::collections::{HashMap, HashSet};
 
-use itertools::Itertools;
 use winnow::{ascii::dec_uint, combinator::separated_pair, prelude::*};
 
 type Point = (u32, u32);
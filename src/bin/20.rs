@@ -23,6 +23,7 @@ fn find_start(maze: &[&[u8]]) -> Option<Point> {
     find(maze, b'S')
 }
 
+#[allow(dead_code)]
 fn find_end(maze: &[&[u8]]) -> Option<Point> {
     find(maze, b'E')
 }
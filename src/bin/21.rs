@@ -1,7 +1,5 @@
 use std::collections::HashMap;
 
-use itertools::Itertools;
-
 advent_of_code::solution!(21);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -0,0 +1,7 @@
+// Aggregate binary that links every scaffolded day and runs them in-process.
+// Invoked by `run_multi` as `all <puzzle> [--time]`, e.g. `all 01` or `all 2023-01`.
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+fn main() {
+    advent_of_code::template::run_registered(SOLUTIONS);
+}
@@ -1,81 +1,229 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, scaffold, solve, stars, time, verify, watch,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, Puzzle};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::scaffold;
+    use advent_of_code::template::commands::watch::WatchAction;
+    use advent_of_code::template::{compare, Day, Puzzle, Timeouts, Year};
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: Puzzle,
         },
         Read {
-            day: Day,
+            puzzle: Puzzle,
+        },
+        Examples {
+            puzzle: Puzzle,
+            pick: Option<usize>,
+            /// Part suffix of the example file to write.
+            example: Option<u8>,
+            overwrite: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: Puzzle,
             download: bool,
             overwrite: bool,
+            template: String,
+            answer_type: String,
         },
         Solve {
-            day: Day,
+            puzzle: Puzzle,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: Option<String>,
+            /// `--example` with an optional part suffix.
+            example: Option<Option<u8>>,
         },
         All {
+            year: Option<Year>,
             release: bool,
+            timeouts: Timeouts,
+            /// Number of days that run in parallel.
+            jobs: usize,
         },
         Time {
+            year: Option<Year>,
             all: bool,
             day: Option<Day>,
             store: bool,
+            reject_outliers: bool,
+            compare: Option<f64>,
+            history: Option<Day>,
+            timeouts: Timeouts,
+        },
+        Verify {
+            year: Option<Year>,
+            day: Option<Day>,
+            timeouts: Timeouts,
+        },
+        Stars {
+            year: Option<Year>,
+            /// Only award stars to parts whose solutions still produce the accepted answers.
+            verify: bool,
+            timeouts: Timeouts,
+        },
+        WatchDay {
+            puzzle: Puzzle,
+            action: WatchAction,
+            release: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Option<Year>,
+        },
+    }
+
+    /// Parses a day, checking that it is part of the year's event.
+    fn parse_day(year: Option<Year>, s: &str) -> Result<Day, Box<dyn std::error::Error>> {
+        Day::parse_in(s, year).map_err(|e| format!("failed to parse '{s}': {e}").into())
+    }
+
+    fn opt_day(
+        year: Option<Year>,
+        s: Option<String>,
+    ) -> Result<Option<Day>, Box<dyn std::error::Error>> {
+        s.map(|s| parse_day(year, &s)).transpose()
+    }
+
+    fn puzzle(
+        year: Option<Year>,
+        args: &mut pico_args::Arguments,
+    ) -> Result<Puzzle, Box<dyn std::error::Error>> {
+        let day: String = args.free_from_str()?;
+        Ok(Puzzle::new(year, parse_day(year, &day)?))
+    }
+
+    /// Parses a timeout in seconds, e.g. `30` or `2.5`.
+    fn parse_seconds(s: &str) -> Result<Duration, String> {
+        let secs: f64 = s
+            .parse()
+            .map_err(|_| format!("invalid number of seconds: {s}"))?;
+        Duration::try_from_secs_f64(secs).map_err(|e| format!("invalid timeout {s}: {e}"))
+    }
+
+    fn timeouts(args: &mut pico_args::Arguments) -> Result<Timeouts, pico_args::Error> {
+        Ok(Timeouts {
+            day: args.opt_value_from_fn("--timeout", parse_seconds)?,
+            part: args.opt_value_from_fn("--part-timeout", parse_seconds)?,
+        })
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // every subcommand is scoped to a year, omitting it selects the default year (`AOC_YEAR`).
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
                 release: args.contains("--release"),
+                timeouts: timeouts(&mut args)?,
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let reject_outliers = args.contains("--reject-outliers");
+                let compare = args.contains("--compare");
+                let threshold = args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(compare::DEFAULT_THRESHOLD);
+                let history = opt_day(year, args.opt_value_from_str("--history")?)?;
+                let timeouts = timeouts(&mut args)?;
 
                 AppArguments::Time {
+                    year,
                     all,
-                    day: args.opt_free_from_str()?,
+                    day: opt_day(year, args.opt_free_from_str()?)?,
                     store,
+                    reject_outliers,
+                    compare: compare.then_some(threshold),
+                    history,
+                    timeouts,
                 }
             }
+            Some("verify") => {
+                let timeouts = timeouts(&mut args)?;
+
+                AppArguments::Verify {
+                    year,
+                    day: opt_day(year, args.opt_free_from_str()?)?,
+                    timeouts,
+                }
+            }
+            Some("stars") => AppArguments::Stars {
+                year,
+                verify: args.contains("--verify"),
+                timeouts: timeouts(&mut args)?,
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: puzzle(year, &mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: puzzle(year, &mut args)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: puzzle(year, &mut args)?,
+                pick: args.opt_value_from_str("--pick")?,
+                example: args.opt_value_from_str("--example")?,
+                overwrite: args.contains("--overwrite"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: puzzle(year, &mut args)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| scaffold::DEFAULT_TEMPLATE.into()),
+                answer_type: args
+                    .opt_value_from_str("--answer-type")?
+                    .unwrap_or_else(|| scaffold::DEFAULT_ANSWER_TYPE.into()),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: puzzle(year, &mut args)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                input: args.opt_value_from_str("--input")?,
+                // the part suffix of `--example` is optional.
+                example: match args.opt_value_from_str("--example") {
+                    Ok(Some(part)) => Some(Some(part)),
+                    _ => args.contains("--example").then_some(None),
+                },
             },
+            Some("watch-day") => {
+                let action = if args.contains("--solve") {
+                    WatchAction::Solve
+                } else if args.contains("--example") {
+                    WatchAction::Example
+                } else {
+                    // `--test` is the default.
+                    args.contains("--test");
+                    WatchAction::Test
+                };
+                let release = args.contains("--release");
+
+                AppArguments::WatchDay {
+                    puzzle: puzzle(year, &mut args)?,
+                    action,
+                    release,
+                }
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today { year },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -102,38 +250,85 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                year,
+                release,
+                timeouts,
+                jobs,
+            } => all::handle(year, release, timeouts, jobs),
+            AppArguments::Time {
+                year,
                 day,
+                all,
+                store,
+                reject_outliers,
+                compare,
+                history,
+                timeouts,
+            } => match history {
+                Some(day) => time::handle_history(year, day),
+                None => time::handle(year, day, all, store, reject_outliers, compare, timeouts),
+            },
+            AppArguments::Verify {
+                year,
+                day,
+                timeouts,
+            } => verify::handle(year, day, timeouts),
+            AppArguments::Stars {
+                year,
+                verify,
+                timeouts,
+            } => stars::handle(year, verify, timeouts),
+            AppArguments::WatchDay {
+                puzzle,
+                action,
+                release,
+            } => watch::handle(puzzle, action, release),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples {
+                puzzle,
+                pick,
+                example,
+                overwrite,
+            } => examples::handle(puzzle, pick, example, overwrite),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
+                template,
+                answer_type,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, overwrite, &template, &answer_type);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+                example,
+            } => solve::handle(puzzle, release, dhat, submit, input, example),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+            AppArguments::Today { year } => {
+                match Day::today().and_then(|day| Puzzle::try_new(year, day)) {
+                    Some(puzzle) => {
+                        scaffold::handle(
+                            puzzle,
+                            false,
+                            scaffold::DEFAULT_TEMPLATE,
+                            scaffold::DEFAULT_ANSWER_TYPE,
+                        );
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run during the days of this year's event \
+                            in december. Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,416 @@
+/// Local ledger of submitted answers, used to avoid resubmitting answers that are known to be wrong.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{Hint, SubmissionOutcome};
+use crate::template::history;
+use crate::template::{data_dir, Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// A single submitted answer and the server's verdict.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmissionOutcome,
+    /// When the answer was submitted, as an ISO 8601 UTC timestamp.
+    pub date: Option<String>,
+}
+
+/// Every answer that was submitted, in submission order.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Submission>,
+}
+
+/// Reason why an answer is not submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// The answer was submitted before and rejected.
+    AlreadyRejected(Option<Hint>),
+    /// The answer is not below an answer that was rejected as too high.
+    NotBelow(String),
+    /// The answer is not above an answer that was rejected as too low.
+    NotAbove(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyRejected(Some(Hint::TooHigh)) => {
+                write!(f, "this answer was already rejected as too high.")
+            }
+            Rejection::AlreadyRejected(Some(Hint::TooLow)) => {
+                write!(f, "this answer was already rejected as too low.")
+            }
+            Rejection::AlreadyRejected(None) => write!(f, "this answer was already rejected."),
+            Rejection::NotBelow(bound) => {
+                write!(
+                    f,
+                    "the answer must be lower than {bound}, which is too high."
+                )
+            }
+            Rejection::NotAbove(bound) => {
+                write!(
+                    f,
+                    "the answer must be higher than {bound}, which is too low."
+                )
+            }
+        }
+    }
+}
+
+impl Answers {
+    /// Dehydrate the ledger of a year to a JSON file.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let dir = data_dir(year);
+        fs::create_dir_all(&dir)?;
+        let mut file = fs::File::create(dir.join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the ledger of a year from a JSON file. If not present, returns an empty ledger.
+    /// A ledger that can not be read or is malformed is an error, so that it is never overwritten.
+    pub fn read_from_file(year: Option<Year>) -> Result<Self, String> {
+        let path = data_dir(year).join(ANSWERS_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(s) => Answers::try_from(s.as_str())
+                .map_err(|e| format!("Failed to read {}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, outcome: SubmissionOutcome) {
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            date: Some(history::now()),
+        });
+    }
+
+    fn for_part(&self, day: Day, part: u8) -> impl Iterator<Item = &Submission> {
+        self.data
+            .iter()
+            .filter(move |s| s.day == day && s.part == part)
+    }
+
+    /// The answer that was accepted for a part, if any.
+    pub fn accepted(&self, day: Day, part: u8) -> Option<&str> {
+        self.for_part(day, part)
+            .find(|s| s.outcome == SubmissionOutcome::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// The date an accepted answer was submitted for a part, e.g. `2024-12-01`.
+    pub fn solved_date(&self, day: Day, part: u8) -> Option<&str> {
+        self.for_part(day, part)
+            .find(|s| s.outcome == SubmissionOutcome::Correct)
+            .and_then(|s| s.date.as_deref())
+            .and_then(|date| date.get(..10))
+    }
+
+    /// The exclusive (lower, upper) bounds of a part, derived from answers that were rejected as too low or too high.
+    pub fn bounds(&self, day: Day, part: u8) -> (Option<i128>, Option<i128>) {
+        let mut lower: Option<i128> = None;
+        let mut upper: Option<i128> = None;
+
+        for submission in self.for_part(day, part) {
+            let Ok(value) = submission.answer.trim().parse::<i128>() else {
+                continue;
+            };
+
+            match submission.outcome {
+                SubmissionOutcome::Incorrect(Some(Hint::TooLow)) => {
+                    lower = Some(lower.map_or(value, |l| l.max(value)));
+                }
+                SubmissionOutcome::Incorrect(Some(Hint::TooHigh)) => {
+                    upper = Some(upper.map_or(value, |u| u.min(value)));
+                }
+                _ => {}
+            }
+        }
+
+        (lower, upper)
+    }
+
+    /// Check whether an answer is worth submitting, based on previous submissions.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Rejection> {
+        let answer = answer.trim();
+
+        let previous = self.for_part(day, part).find(|s| {
+            s.answer.trim() == answer && matches!(s.outcome, SubmissionOutcome::Incorrect(_))
+        });
+
+        if let Some(Submission {
+            outcome: SubmissionOutcome::Incorrect(hint),
+            ..
+        }) = previous
+        {
+            return Err(Rejection::AlreadyRejected(*hint));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let (lower, upper) = self.bounds(day, part);
+
+            if let Some(upper) = upper.filter(|u| value >= *u) {
+                return Err(Rejection::NotBelow(upper.to_string()));
+            }
+
+            if let Some(lower) = lower.filter(|l| value <= *l) {
+                return Err(Rejection::NotAbove(lower.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn outcome_to_str(outcome: &SubmissionOutcome) -> &'static str {
+    match outcome {
+        SubmissionOutcome::Correct => "correct",
+        SubmissionOutcome::Incorrect(None) => "incorrect",
+        SubmissionOutcome::Incorrect(Some(Hint::TooHigh)) => "too_high",
+        SubmissionOutcome::Incorrect(Some(Hint::TooLow)) => "too_low",
+        SubmissionOutcome::AlreadySolved => "already_solved",
+        SubmissionOutcome::RateLimited(_) => "rate_limited",
+        SubmissionOutcome::WrongLevel => "wrong_level",
+        SubmissionOutcome::Unrecognized(_) => "unrecognized",
+    }
+}
+
+fn outcome_from_str(value: &str) -> Option<SubmissionOutcome> {
+    match value {
+        "correct" => Some(SubmissionOutcome::Correct),
+        "incorrect" => Some(SubmissionOutcome::Incorrect(None)),
+        "too_high" => Some(SubmissionOutcome::Incorrect(Some(Hint::TooHigh))),
+        "too_low" => Some(SubmissionOutcome::Incorrect(Some(Hint::TooLow))),
+        "already_solved" => Some(SubmissionOutcome::AlreadySolved),
+        "rate_limited" => Some(SubmissionOutcome::RateLimited(None)),
+        "wrong_level" => Some(SubmissionOutcome::WrongLevel),
+        "unrecognized" => Some(SubmissionOutcome::Unrecognized(String::new())),
+        _ => None,
+    }
+}
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Answers {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(outcome_to_str(&value.outcome).into()),
+        );
+        if let Some(date) = &value.date {
+            map.insert("date".into(), JsonValue::String(date.clone()));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .ok_or(format!("Expected submission.{key} to be a string."))
+        };
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.part to be a number.")?;
+
+        Ok(Submission {
+            day: string("day")?
+                .parse()
+                .ok()
+                .and_then(Day::new)
+                .ok_or("Expected submission.day to be a valid day.")?,
+            part: *part as u8,
+            answer: string("answer")?.clone(),
+            outcome: outcome_from_str(string("outcome")?)
+                .ok_or("Expected submission.outcome to be a known outcome.")?,
+            // submissions recorded before dates were recorded have no `date` key.
+            date: json.get("date").and_then(|v| v.get::<String>()).cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Answers, Rejection};
+    use crate::{
+        day,
+        template::aoc_client::{Hint, SubmissionOutcome},
+    };
+
+    fn ledger() -> Answers {
+        let mut answers = Answers::default();
+        answers.record(
+            day!(1),
+            1,
+            "100",
+            SubmissionOutcome::Incorrect(Some(Hint::TooHigh)),
+        );
+        answers.record(
+            day!(1),
+            1,
+            "10",
+            SubmissionOutcome::Incorrect(Some(Hint::TooLow)),
+        );
+        answers.record(day!(1), 1, "50", SubmissionOutcome::Incorrect(None));
+        answers.record(
+            day!(1),
+            1,
+            "80",
+            SubmissionOutcome::Incorrect(Some(Hint::TooHigh)),
+        );
+        answers.record(day!(1), 2, "abc", SubmissionOutcome::Incorrect(None));
+        answers
+    }
+
+    #[test]
+    fn derives_bounds() {
+        let answers = ledger();
+        assert_eq!(answers.bounds(day!(1), 1), (Some(10), Some(80)));
+        assert_eq!(answers.bounds(day!(1), 2), (None, None));
+        assert_eq!(answers.bounds(day!(2), 1), (None, None));
+    }
+
+    #[test]
+    fn blocks_known_wrong_answers() {
+        let answers = ledger();
+        assert_eq!(
+            answers.check(day!(1), 1, "50"),
+            Err(Rejection::AlreadyRejected(None))
+        );
+        assert_eq!(
+            answers.check(day!(1), 1, "100"),
+            Err(Rejection::AlreadyRejected(Some(Hint::TooHigh)))
+        );
+        assert_eq!(
+            answers.check(day!(1), 2, "abc"),
+            Err(Rejection::AlreadyRejected(None))
+        );
+    }
+
+    #[test]
+    fn blocks_answers_outside_of_bounds() {
+        let answers = ledger();
+        assert_eq!(
+            answers.check(day!(1), 1, "90"),
+            Err(Rejection::NotBelow("80".into()))
+        );
+        assert_eq!(
+            answers.check(day!(1), 1, "5"),
+            Err(Rejection::NotAbove("10".into()))
+        );
+        assert_eq!(answers.check(day!(1), 1, "42"), Ok(()));
+        assert_eq!(answers.check(day!(1), 2, "90"), Ok(()));
+    }
+
+    #[test]
+    fn finds_accepted_answers() {
+        let mut answers = ledger();
+        assert_eq!(answers.accepted(day!(1), 1), None);
+        answers.record(day!(1), 1, "42", SubmissionOutcome::Correct);
+        assert_eq!(answers.accepted(day!(1), 1), Some("42"));
+        assert_eq!(answers.accepted(day!(1), 2), None);
+    }
+
+    #[test]
+    fn ignores_answers_that_were_not_evaluated() {
+        let mut answers = Answers::default();
+        answers.record(day!(3), 1, "7", SubmissionOutcome::RateLimited(None));
+        assert_eq!(answers.check(day!(3), 1, "7"), Ok(()));
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let answers = ledger();
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        let parsed = Answers::try_from(json.as_str()).unwrap();
+        assert_eq!(parsed.data, answers.data);
+    }
+
+    #[test]
+    fn finds_solved_dates() {
+        let json = r#"{ "data": [
+            { "day": "01", "part": 1, "answer": "41", "outcome": "incorrect", "date": "2024-12-01T05:10:00Z" },
+            { "day": "01", "part": 1, "answer": "42", "outcome": "correct", "date": "2024-12-02T05:20:00Z" },
+            { "day": "01", "part": 2, "answer": "7", "outcome": "correct" }
+        ] }"#;
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.solved_date(day!(1), 1), Some("2024-12-02"));
+        assert_eq!(answers.solved_date(day!(1), 2), None);
+        assert_eq!(answers.accepted(day!(1), 2), Some("7"));
+    }
+}
@@ -0,0 +1,489 @@
+/// Built-in client for the Advent of Code website.
+/// Reads the session token from the `AOC_SESSION` environment variable or from `~/.adventofcode.session`.
+/// The base url defaults to `https://adventofcode.com` and can be overridden with `AOC_BASE_URL`.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{html, Puzzle};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+const USER_AGENT: &str = "github.com/arades79/aoc-2024 (advent_of_code template)";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    MissingYear,
+    Http(u16, String),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session token found. Set `AOC_SESSION` or create `~/{SESSION_FILE_NAME}`."
+            ),
+            AocClientError::MissingYear => write!(f, "`AOC_YEAR` is not set to a valid year."),
+            AocClientError::Http(status, body) => {
+                write!(f, "server responded with status {status}: {}", body.trim())
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                AocClientError::Http(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// Direction hint given by the server for an incorrect answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// The verdict of the server on a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect(Option<Hint>),
+    AlreadySolved,
+    /// An answer was submitted too recently. Holds the remaining wait time, if the server reported one.
+    RateLimited(Option<Duration>),
+    /// The submitted part is not unlocked yet.
+    WrongLevel,
+    /// The response did not match any known message. Holds the message as markdown.
+    Unrecognized(String),
+}
+
+impl SubmissionOutcome {
+    /// Parse the response message of an answer submission.
+    /// The server uses the same message for already solved and locked parts, which is reported as [`SubmissionOutcome::WrongLevel`].
+    pub fn parse(message: &str) -> Self {
+        let normalized = message.to_ascii_lowercase();
+
+        if normalized.contains("that's the right answer") {
+            SubmissionOutcome::Correct
+        } else if normalized.contains("that's not the right answer") {
+            let hint = if normalized.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if normalized.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            SubmissionOutcome::Incorrect(hint)
+        } else if normalized.contains("you gave an answer too recently") {
+            SubmissionOutcome::RateLimited(parse_wait_time(&normalized))
+        } else if normalized.contains("you don't seem to be solving the right level") {
+            SubmissionOutcome::WrongLevel
+        } else {
+            SubmissionOutcome::Unrecognized(message.trim().to_string())
+        }
+    }
+
+    /// Exit code of `cargo solve --submit` for this outcome.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            SubmissionOutcome::Correct => 0,
+            SubmissionOutcome::Incorrect(_) => 2,
+            SubmissionOutcome::RateLimited(_) => 3,
+            SubmissionOutcome::AlreadySolved => 4,
+            SubmissionOutcome::WrongLevel => 5,
+            SubmissionOutcome::Unrecognized(_) => 6,
+        }
+    }
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "✔ That's the right answer!"),
+            SubmissionOutcome::Incorrect(Some(Hint::TooHigh)) => {
+                write!(f, "✖ That's not the right answer, it is too high.")
+            }
+            SubmissionOutcome::Incorrect(Some(Hint::TooLow)) => {
+                write!(f, "✖ That's not the right answer, it is too low.")
+            }
+            SubmissionOutcome::Incorrect(None) => write!(f, "✖ That's not the right answer."),
+            SubmissionOutcome::AlreadySolved => write!(f, "This part is already solved."),
+            SubmissionOutcome::RateLimited(Some(wait)) => write!(
+                f,
+                "⏳ You submitted an answer too recently, wait {wait:?} before trying again."
+            ),
+            SubmissionOutcome::RateLimited(None) => write!(
+                f,
+                "⏳ You submitted an answer too recently, wait a bit before trying again."
+            ),
+            SubmissionOutcome::WrongLevel => write!(f, "This part is not unlocked yet."),
+            SubmissionOutcome::Unrecognized(message) => {
+                write!(f, "Unrecognized response:\n{message}")
+            }
+        }
+    }
+}
+
+/// Parse the remaining wait time of a rate-limited response, e.g. `you have 1m 23s left to wait`.
+fn parse_wait_time(message: &str) -> Option<Duration> {
+    let start = message.rfind("you have ")? + "you have ".len();
+    let end = start + message[start..].find(" left to wait")?;
+
+    let mut secs = 0;
+    for token in message[start..end].split_whitespace() {
+        let unit = match token.chars().last()? {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        let value: u64 = token[..token.len() - 1].parse().ok()?;
+        secs += value * unit;
+    }
+
+    Some(Duration::from_secs(secs))
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Create a client from the environment and the session file.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = get_session().ok_or(AocClientError::MissingSession)?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    fn day_url(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let year = puzzle.year().ok_or(AocClientError::MissingYear)?;
+        Ok(format!(
+            "{}/{year}/day/{}",
+            self.base_url,
+            puzzle.day.into_inner()
+        ))
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetch the personal puzzle input.
+    pub fn fetch_input(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(puzzle)?))
+    }
+
+    /// Fetch the puzzle description, converted to markdown.
+    pub fn fetch_puzzle(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let page = self.get(&self.day_url(puzzle)?)?;
+        Ok(html::puzzle_to_markdown(&page))
+    }
+
+    /// Post an answer and parse the server's verdict.
+    pub fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(puzzle)?))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let message = html::response_to_markdown(&response.into_string()?);
+
+        match SubmissionOutcome::parse(&message) {
+            // the puzzle page lists an accepted answer for every solved part.
+            SubmissionOutcome::WrongLevel => {
+                let page = self.get(&self.day_url(puzzle)?)?;
+                let solved_parts = page.matches("Your puzzle answer was").count();
+                if solved_parts >= usize::from(part) {
+                    Ok(SubmissionOutcome::AlreadySolved)
+                } else {
+                    Ok(SubmissionOutcome::WrongLevel)
+                }
+            }
+            outcome => Ok(outcome),
+        }
+    }
+}
+
+pub fn read(puzzle: Puzzle) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle_path = get_puzzle_path(puzzle)?;
+    let description = client.fetch_puzzle(puzzle)?;
+    fs::write(puzzle_path, &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+pub fn download(puzzle: Puzzle) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = get_input_path(puzzle)?;
+    let puzzle_path = get_puzzle_path(puzzle)?;
+
+    fs::write(&input_path, client.fetch_input(puzzle)?)?;
+    fs::write(&puzzle_path, client.fetch_puzzle(puzzle)?)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to {input_path:?}.");
+    println!("🎄 Successfully wrote puzzle to {puzzle_path:?}.");
+    Ok(())
+}
+
+pub fn submit(puzzle: Puzzle, part: u8, result: &str) -> Result<SubmissionOutcome, AocClientError> {
+    AocClient::from_env()?.submit(puzzle, part, result)
+}
+
+/// Path of a puzzle's file in a data folder, creating the folder if it does not exist yet.
+fn get_data_path(puzzle: Puzzle, folder: &str, extension: &str) -> Result<PathBuf, io::Error> {
+    let dir = puzzle.data_dir().join(folder);
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(format!("{}.{extension}", puzzle.day)))
+}
+
+fn get_input_path(puzzle: Puzzle) -> Result<PathBuf, io::Error> {
+    get_data_path(puzzle, "inputs", "txt")
+}
+
+fn get_puzzle_path(puzzle: Puzzle) -> Result<PathBuf, io::Error> {
+    get_data_path(puzzle, "puzzles", "md")
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let session = fs::read_to_string(PathBuf::from(home).join(SESSION_FILE_NAME)).ok()?;
+    Some(session.trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    use super::{AocClient, AocClientError, Hint, SubmissionOutcome};
+    use crate::template::Puzzle;
+    use crate::{day, year};
+
+    /// A stand-in server that answers a single request with `body` and reports the raw request.
+    fn serve_once(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        serve(vec![(status, body)])
+    }
+
+    /// A stand-in server that answers one request per response, in order, and reports the raw requests.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+
+                let mut payload = vec![0; content_length];
+                reader.read_exact(&mut payload).unwrap();
+                request.push_str(&String::from_utf8(payload).unwrap());
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+
+                let _ = tx.send(request);
+            }
+        });
+
+        (url, rx)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (url, requests) = serve_once(200, "1 2 3\n");
+        let client = AocClient::new(&url, "secret");
+        assert_eq!(
+            client
+                .fetch_input(Puzzle::new(Some(year!(2023)), day!(5)))
+                .unwrap(),
+            "1 2 3\n"
+        );
+        let request = requests.recv().unwrap();
+        assert_eq!(request.starts_with("GET /2023/day/5/input "), true);
+        assert_eq!(request.contains("session=secret"), true);
+    }
+
+    #[test]
+    fn fetches_puzzle_as_markdown() {
+        let (url, _) = serve_once(
+            200,
+            "<main><article><h2>--- Day 1: Test ---</h2><p>Hello <em>world</em></p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret");
+        assert_eq!(
+            client
+                .fetch_puzzle(Puzzle::new(Some(year!(2023)), day!(1)))
+                .unwrap(),
+            "\\--- Day 1: Test ---\n----------\n\nHello *world*"
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, requests) = serve_once(200, "<article><p>That's the right answer!</p></article>");
+        let client = AocClient::new(&url, "secret");
+        assert_eq!(
+            client
+                .submit(Puzzle::new(Some(year!(2023)), day!(12)), 2, "1234")
+                .unwrap(),
+            SubmissionOutcome::Correct
+        );
+        let request = requests.recv().unwrap();
+        assert_eq!(request.starts_with("POST /2023/day/12/answer "), true);
+        assert_eq!(request.ends_with("level=2&answer=1234"), true);
+    }
+
+    #[test]
+    fn reports_http_errors() {
+        let (url, _) = serve_once(404, "Not found");
+        let client = AocClient::new(&url, "secret");
+        match client.fetch_input(Puzzle::new(Some(year!(2023)), day!(25))) {
+            Err(AocClientError::Http(404, body)) => assert_eq!(body, "Not found"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_submission_outcomes() {
+        assert_eq!(
+            SubmissionOutcome::parse("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data."),
+            SubmissionOutcome::Incorrect(Some(Hint::TooHigh))
+        );
+        assert_eq!(
+            SubmissionOutcome::parse("That's not the right answer; your answer is too low."),
+            SubmissionOutcome::Incorrect(Some(Hint::TooLow))
+        );
+        assert_eq!(
+            SubmissionOutcome::parse(
+                "That's not the right answer.  Please wait one minute before trying again."
+            ),
+            SubmissionOutcome::Incorrect(None)
+        );
+        assert_eq!(
+            SubmissionOutcome::parse("You don't seem to be solving the right level.  Did you already complete it? [[Return to Day 1]](/2023/day/1)"),
+            SubmissionOutcome::WrongLevel
+        );
+        assert_eq!(
+            SubmissionOutcome::parse("Something else entirely."),
+            SubmissionOutcome::Unrecognized("Something else entirely.".into())
+        );
+    }
+
+    #[test]
+    fn parses_rate_limit_wait_time() {
+        let message = "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.";
+        assert_eq!(
+            SubmissionOutcome::parse(message),
+            SubmissionOutcome::RateLimited(Some(Duration::from_secs(83)))
+        );
+        assert_eq!(
+            SubmissionOutcome::parse("You gave an answer too recently."),
+            SubmissionOutcome::RateLimited(None)
+        );
+    }
+
+    #[test]
+    fn detects_already_solved_parts() {
+        let (url, requests) = serve(vec![
+            (200, "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>"),
+            (200, "<article><h2>--- Day 1 ---</h2></article><p>Your puzzle answer was <code>1</code>.</p>"),
+        ]);
+        let client = AocClient::new(&url, "secret");
+        assert_eq!(
+            client
+                .submit(Puzzle::new(Some(year!(2023)), day!(1)), 1, "1")
+                .unwrap(),
+            SubmissionOutcome::AlreadySolved
+        );
+        assert_eq!(
+            requests
+                .recv()
+                .unwrap()
+                .starts_with("POST /2023/day/1/answer "),
+            true
+        );
+        assert_eq!(
+            requests.recv().unwrap().starts_with("GET /2023/day/1 "),
+            true
+        );
+    }
+
+    #[test]
+    fn maps_outcomes_to_exit_codes() {
+        assert_eq!(SubmissionOutcome::Correct.exit_code(), 0);
+        assert_eq!(SubmissionOutcome::Incorrect(None).exit_code(), 2);
+        assert_eq!(SubmissionOutcome::RateLimited(None).exit_code(), 3);
+        assert_eq!(SubmissionOutcome::AlreadySolved.exit_code(), 4);
+        assert_eq!(SubmissionOutcome::WrongLevel.exit_code(), 5);
+    }
+}
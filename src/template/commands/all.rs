@@ -1,5 +1,19 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{all_days_in, run_multi::run_multi, Timeouts, Year};
+
+pub fn handle(year: Option<Year>, is_release: bool, timeouts: Timeouts, jobs: usize) {
+    let run = run_multi(
+        year,
+        &all_days_in(year).collect(),
+        is_release,
+        false,
+        false,
+        timeouts,
+        jobs,
+    );
+
+    if run.has_failures() {
+        process::exit(1);
+    }
 }
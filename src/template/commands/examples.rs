@@ -0,0 +1,136 @@
+use std::{fs, process};
+
+use crate::template::examples::{Example, ExampleManifest};
+use crate::template::extract::{self, Candidate};
+use crate::template::Puzzle;
+
+/// Number of lines of each candidate that is shown in the listing.
+const PREVIEW_LINES: usize = 5;
+
+/// Lists the code blocks of a puzzle description, or writes the picked one (1-based) as example file and records its answers in the manifest.
+/// `example` selects the part suffix of the example file, e.g. `01-2.txt`, like `cargo solve --example <N>`.
+pub fn handle(puzzle: Puzzle, pick: Option<usize>, example: Option<u8>, overwrite: bool) {
+    let day = puzzle.day;
+    let puzzle_path = puzzle.data_dir().join("puzzles").join(format!("{day}.md"));
+
+    let markdown = match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => markdown,
+        Err(e) => {
+            eprintln!("Failed to read puzzle description {puzzle_path:?}: {e}");
+            eprintln!("Download it with `cargo download {day}` or `cargo read {day}` first.");
+            process::exit(1);
+        }
+    };
+
+    let candidates = extract::candidates(&markdown);
+    if candidates.is_empty() {
+        eprintln!("Found no code blocks in {puzzle_path:?}.");
+        process::exit(1);
+    }
+
+    match pick {
+        None => print_candidates(&candidates, day.into_inner()),
+        Some(index) => match index.checked_sub(1).and_then(|i| candidates.get(i)) {
+            Some(candidate) => write_example(puzzle, candidate, example, overwrite),
+            None => {
+                eprintln!(
+                    "There is no example {index}, pick one of 1 to {}.",
+                    candidates.len()
+                );
+                process::exit(1);
+            }
+        },
+    }
+}
+
+fn print_candidates(candidates: &[Candidate], day: u8) {
+    for (i, candidate) in candidates.iter().enumerate() {
+        let lines: Vec<&str> = candidate.input.lines().collect();
+        println!(
+            "Example {} (part {}, {} lines):",
+            i + 1,
+            candidate.part,
+            lines.len()
+        );
+
+        for line in lines.iter().take(PREVIEW_LINES) {
+            println!("    {line}");
+        }
+        if lines.len() > PREVIEW_LINES {
+            println!("    ...");
+        }
+
+        for part in [1, 2] {
+            let emphasized = &candidate.emphasized[usize::from(part - 1)];
+            if let Some(answer) = candidate.answer(part) {
+                println!(
+                    "  Part {part}: {answer} (emphasized: {})",
+                    emphasized.join(", ")
+                );
+            }
+        }
+        println!();
+    }
+
+    println!("---");
+    println!("🎄 Type `cargo examples {day} --pick <N>` to write an example file.");
+}
+
+fn write_example(puzzle: Puzzle, candidate: &Candidate, example: Option<u8>, overwrite: bool) {
+    let day = puzzle.day;
+    let file = match example {
+        Some(part) => format!("{day}-{part}.txt"),
+        None => format!("{day}.txt"),
+    };
+    let example_path = puzzle.data_dir().join("examples").join(&file);
+
+    // scaffolding creates an empty example file, which is fine to replace.
+    let existing = fs::read_to_string(&example_path).unwrap_or_default();
+    if !overwrite && !existing.trim().is_empty() && existing != candidate.input {
+        eprintln!("Example file {example_path:?} already exists, pass --overwrite to replace it.");
+        process::exit(1);
+    }
+
+    if let Err(e) = fs::create_dir_all(example_path.parent().unwrap())
+        .and_then(|()| fs::write(&example_path, &candidate.input))
+    {
+        eprintln!("Failed to write example file: {e}");
+        process::exit(1);
+    }
+    println!("Created example file {example_path:?}");
+
+    let mut manifest = match ExampleManifest::read(puzzle) {
+        Ok(manifest) => manifest.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Failed to read example manifest: {e}");
+            process::exit(1);
+        }
+    };
+
+    let entry = Example {
+        file,
+        part_1: candidate.answer(1).map(Into::into),
+        part_2: candidate.answer(2).map(Into::into),
+        ..Default::default()
+    };
+
+    for part in [1, 2] {
+        match entry.expected(part) {
+            Some(answer) => println!("Expected answer of part {part}: {answer}"),
+            None => println!("Found no answer for part {part}."),
+        }
+    }
+
+    manifest.upsert(entry);
+
+    match manifest.store(puzzle) {
+        Ok(()) => println!(
+            "Updated example manifest {:?}",
+            ExampleManifest::path(puzzle)
+        ),
+        Err(e) => {
+            eprintln!("Failed to write example manifest: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,15 +1,84 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::examples::{Example, ExampleManifest};
+use crate::template::{extract, Puzzle};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Directory of the module templates, selected with `--template <name>`.
+const TEMPLATES_DIR: &str = "src/templates";
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// Read a template from the templates directory, listing the available templates if it does not exist.
+fn read_template(name: &str) -> Result<String, String> {
+    let path = Path::new(TEMPLATES_DIR).join(format!("{name}.txt"));
+    fs::read_to_string(&path).map_err(|e| {
+        let mut available: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                (path.extension()? == "txt")
+                    .then(|| path.file_stem()?.to_str().map(String::from))?
+            })
+            .collect();
+        available.sort_unstable();
+        format!(
+            "{path:?}: {e}. Available templates: {}",
+            available.join(", ")
+        )
+    })
+}
+
+/// Fill in the placeholders of a template:
+/// `%YEAR_ARG%` and `%DAY_NUMBER%` for the `solution!` macro, `%YEAR%`, `%DAY%`, `%TITLE%` and `%ANSWER_TYPE%`.
+fn render(template: &str, puzzle: Puzzle, answer_type: &str) -> String {
+    let day = puzzle.day;
+
+    // solutions of other years than the default year name their year.
+    let year_arg = match puzzle.year {
+        Some(year) => format!("year = {year}, "),
+        None => String::new(),
+    };
+
+    // the title is only known once the puzzle description was downloaded.
+    let title = extract::read_title(puzzle).unwrap_or_else(|| format!("Day {}", day.into_inner()));
+
+    template
+        .replace("%YEAR_ARG%", &year_arg)
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace(
+            "%YEAR%",
+            &puzzle.year().map(|y| y.to_string()).unwrap_or_default(),
+        )
+        .replace("%DAY%", &day.to_string())
+        .replace("%TITLE%", &title)
+        .replace("%ANSWER_TYPE%", answer_type)
+}
+
+/// Add an entry for the example file to the day's manifest, keeping existing entries.
+/// Returns whether the entry was added.
+fn add_manifest_entry(puzzle: Puzzle, file: &str) -> Result<bool, String> {
+    let mut manifest = ExampleManifest::read(puzzle)?.unwrap_or_default();
+    if manifest.examples.iter().any(|e| e.file == file) {
+        return Ok(false);
+    }
+
+    manifest.examples.push(Example {
+        file: file.into(),
+        ..Default::default()
+    });
+    manifest.store(puzzle).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +88,10 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +99,20 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: Puzzle, overwrite: bool, template: &str, answer_type: &str) {
+    let day = puzzle.day;
+    let input_path = puzzle.data_dir().join("inputs").join(format!("{day}.txt"));
+    let example_file = format!("{day}.txt");
+    let example_path = puzzle.data_dir().join("examples").join(&example_file);
+    let module_path = Path::new("src/bin").join(format!("{puzzle}.rs"));
+
+    let template = match read_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to read template {e}");
+            process::exit(1);
+        }
+    };
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +122,9 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(render(&template, puzzle, answer_type).as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file {module_path:?}");
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -56,7 +134,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file {input_path:?}");
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -64,16 +142,37 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    // keep an example that was already extracted with `cargo examples`.
+    let has_example = fs::metadata(&example_path).is_ok_and(|m| m.len() > 0);
+    if !has_example {
+        match create_file(&example_path) {
+            Ok(_) => {
+                println!("Created empty example file {example_path:?}");
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    match add_manifest_entry(puzzle, &example_file) {
+        Ok(false) => {}
+        Ok(true) => {
+            println!(
+                "Added {example_file} to example manifest {:?}",
+                ExampleManifest::path(puzzle)
+            );
         }
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to update example manifest: {e}");
             process::exit(1);
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match puzzle.year {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
 }
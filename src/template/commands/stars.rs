@@ -0,0 +1,42 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::commands::verify::run_verdicts;
+use crate::template::readme_stars::{self, DayStars};
+use crate::template::{all_days_in, is_default_year, Day, Timeouts, Year};
+
+pub fn handle(year: Option<Year>, verify: bool, timeouts: Timeouts) {
+    let answers = match Answers::read_from_file(year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let days: Vec<Day> = all_days_in(year)
+        .filter(|day| answers.accepted(*day, 1).is_some() || answers.accepted(*day, 2).is_some())
+        .collect();
+
+    // parts only keep their stars if their solutions still produce the accepted answers.
+    let verdicts =
+        (verify && !days.is_empty()).then(|| run_verdicts(year, days.clone(), &answers, timeouts));
+
+    let stars = readme_stars::collect(&answers, days.into_iter(), verdicts.as_deref());
+    let total: usize = stars.iter().map(DayStars::count).sum();
+    let table = readme_stars::render(year, &stars);
+
+    // the readme only shows the progress of the default year.
+    if !is_default_year(year) {
+        println!("{table}");
+        return;
+    }
+
+    match readme_stars::update(&table) {
+        Ok(()) => println!("Stored {total} ⭐ in the readme."),
+        Err(e) => {
+            eprintln!("Failed to store stars: {e:?}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,19 +1,34 @@
 use std::collections::HashSet;
+use std::process;
 
+use crate::template::history::{self, History, HistoryEntry, RunMetadata};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days_in, compare, is_default_year, readme_benchmarks, Day, Timeouts, Year,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    year: Option<Year>,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    reject_outliers: bool,
+    compare_threshold: Option<f64>,
+    timeouts: Timeouts,
+) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                all_days_in(year).collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, bench every day that has stored timings.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days_in(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,20 +36,65 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let run = run_multi(year, &days_to_run, true, true, reject_outliers, timeouts, 1);
+    let timings = run.timings.clone();
+
+    if !timings.data.is_empty() {
+        let entry = HistoryEntry {
+            metadata: RunMetadata::collect(),
+            timings: timings.clone(),
+        };
+        if let Err(e) = History::append(&entry, year) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+    }
+
+    let has_regressions = compare_threshold.is_some_and(|threshold| {
+        let deltas = compare::compare(&stored_timings, &timings);
+        println!();
+        compare::print_table(&deltas, threshold);
+        deltas.iter().any(|d| d.is_regression(threshold))
+    });
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        if let Err(e) = merged_timings.store_file(year) {
+            eprintln!("Failed to store timings: {e}");
+            process::exit(1);
+        }
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+        // the readme only shows the benchmarks of the default year.
+        if !is_default_year(year) {
+            println!("Stored updated benchmarks.");
+        } else {
+            match readme_benchmarks::update(merged_timings) {
+                Ok(()) => {
+                    println!("Stored updated benchmarks.");
+                }
+                Err(readme_benchmarks::Error::Parser(e)) => {
+                    eprintln!("Failed to store updated benchmarks: {e}");
+                }
+                Err(_) => {
+                    eprintln!("Failed to store updated benchmarks.");
+                }
             }
         }
     }
+
+    if run.has_failures() {
+        eprintln!("Some days failed.");
+    }
+
+    if has_regressions {
+        eprintln!("Some parts regressed beyond the threshold.");
+    }
+
+    if run.has_failures() || has_regressions {
+        process::exit(1);
+    }
+}
+
+pub fn handle_history(year: Option<Year>, day: Day) {
+    history::print_day_history(&History::read_from_file(year), day);
 }
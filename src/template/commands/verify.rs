@@ -0,0 +1,90 @@
+use std::path::Path;
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::report::part_name;
+use crate::template::run_multi::{child_commands, get_path_for_bin, Output, Timeouts};
+use crate::template::verify::{self, DayVerdict};
+use crate::template::{all_days_in, Day, Puzzle, Year};
+
+pub fn handle(year: Option<Year>, day: Option<Day>, timeouts: Timeouts) {
+    let answers = match Answers::read_from_file(year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    // only days that have at least one accepted answer are considered solved.
+    let days: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days_in(year)
+            .filter(|day| {
+                answers.accepted(*day, 1).is_some() || answers.accepted(*day, 2).is_some()
+            })
+            .collect(),
+    };
+
+    if days.is_empty() {
+        println!("No accepted answers recorded, submit solutions with `cargo solve <day> --submit <part>` first.");
+        return;
+    }
+
+    let verdicts = run_verdicts(year, days, &answers, timeouts);
+
+    verify::print_matrix(&verdicts);
+
+    if verdicts.iter().any(DayVerdict::is_failure) {
+        eprintln!("\nSome answers do not match the accepted answers.");
+        process::exit(1);
+    }
+}
+
+/// Run the solutions of `days` in release mode and compare their answers against the accepted answers.
+/// A day whose solution fails to run, crashes or exceeds a timeout fails every part with an accepted answer.
+pub fn run_verdicts(
+    year: Option<Year>,
+    days: Vec<Day>,
+    answers: &Answers,
+    timeouts: Timeouts,
+) -> Vec<DayVerdict> {
+    let Ok(bin) = child_commands::build_solutions(true) else {
+        eprintln!("Failed to build solutions.");
+        process::exit(1);
+    };
+
+    days.into_iter()
+        .map(|day| {
+            let puzzle = Puzzle::new(year, day);
+            let failure = if Path::new(&get_path_for_bin(puzzle)).exists() {
+                match child_commands::run_solution(
+                    &bin,
+                    puzzle,
+                    false,
+                    false,
+                    timeouts,
+                    &mut Output::Silent,
+                ) {
+                    Ok(run) => match (run.timed_out, run.exit_code) {
+                        (Some(part), _) => Err(format!("{} exceeded the timeout", part_name(part))),
+                        (None, Some(0)) => Ok(run.results),
+                        (None, Some(code)) => Err(format!("the solution exited with code {code}")),
+                        (None, None) => Err("the solution was terminated by a signal".into()),
+                    },
+                    Err(e) => Err(format!("failed to run the solution: {e:?}")),
+                }
+            } else {
+                Err("the solution is not scaffolded".into())
+            };
+
+            match failure {
+                Ok(results) => verify::verify_day(answers, day, &results),
+                Err(reason) => {
+                    eprintln!("Day {puzzle}: {reason}.");
+                    verify::verify_day(answers, day, &[])
+                }
+            }
+        })
+        .collect()
+}
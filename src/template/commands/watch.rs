@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use crate::template::commands::solve;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::watch::Snapshot;
+use crate::template::{Puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Clears the terminal and moves the cursor to its top left corner.
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Editors may write a file in several steps, changes are only acted upon once they settled.
+const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// What to run whenever the files of a day change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchAction {
+    /// `cargo test --bin <day>`
+    Test,
+    /// `cargo solve <day>`
+    Solve,
+    /// `cargo solve <day> --example`
+    Example,
+}
+
+pub fn handle(puzzle: Puzzle, action: WatchAction, release: bool) {
+    if !Path::new(&get_path_for_bin(puzzle)).exists() {
+        eprintln!("Day {puzzle} is not scaffolded yet, run `cargo scaffold {puzzle}` first.");
+        process::exit(1);
+    }
+
+    let mut snapshot = Snapshot::take(puzzle);
+    let mut changes: Vec<PathBuf> = vec![];
+
+    loop {
+        run(puzzle, action, release, &changes);
+        println!(
+            "\n{ANSI_ITALIC}Watching {} files for changes, press Ctrl+C to stop.{ANSI_RESET}",
+            snapshot.paths().count()
+        );
+        (snapshot, changes) = wait_for_changes(puzzle, &snapshot);
+    }
+}
+
+/// Poll the files of a day until one of them changes. Returns the new snapshot and the changed files.
+fn wait_for_changes(puzzle: Puzzle, previous: &Snapshot) -> (Snapshot, Vec<PathBuf>) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        if Snapshot::take(puzzle).changes(previous).is_empty() {
+            continue;
+        }
+
+        thread::sleep(SETTLE_DELAY);
+        let snapshot = Snapshot::take(puzzle);
+        let changes = snapshot.changes(previous);
+        return (snapshot, changes);
+    }
+}
+
+fn run(puzzle: Puzzle, action: WatchAction, release: bool, changes: &[PathBuf]) {
+    let args = match action {
+        WatchAction::Test => {
+            let mut args = vec!["test".to_string(), "--bin".to_string(), puzzle.to_string()];
+            if release {
+                args.push("--release".to_string());
+            }
+            args
+        }
+        WatchAction::Solve => solve::cargo_args(puzzle, release, false, None, None, None),
+        WatchAction::Example => solve::cargo_args(puzzle, release, false, None, None, Some(None)),
+    };
+
+    print!("{ANSI_CLEAR}");
+    println!("{ANSI_BOLD}cargo {}{ANSI_RESET}", args.join(" "));
+    if !changes.is_empty() {
+        let changes: Vec<String> = changes.iter().map(|p| p.display().to_string()).collect();
+        println!("{ANSI_ITALIC}Changed: {}{ANSI_RESET}", changes.join(", "));
+    }
+    println!();
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    let (passed, failed) = match action {
+        WatchAction::Test => ("tests passed", "tests failed"),
+        WatchAction::Solve | WatchAction::Example => ("solved", "failed"),
+    };
+
+    match status {
+        Ok(status) if status.success() => println!("\n{ANSI_BOLD}✔ {passed}{ANSI_RESET}"),
+        Ok(status) => match status.code() {
+            Some(code) => println!("\n{ANSI_BOLD}✖ {failed} (exit code {code}){ANSI_RESET}"),
+            None => println!("\n{ANSI_BOLD}✖ {failed} (terminated by a signal){ANSI_RESET}"),
+        },
+        Err(e) => eprintln!("\nFailed to run cargo: {e}"),
+    }
+}
@@ -0,0 +1,237 @@
+/// Module that compares fresh benchmark results against stored timings.
+use std::time::Duration;
+
+use crate::template::protocol::PARSE_PART;
+use crate::template::stats::Stats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Default regression threshold, in percent.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// The change of a single part's median between the stored and the current run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    pub stored: Option<Duration>,
+    pub current: Option<Duration>,
+}
+
+impl PartDelta {
+    /// Relative change in percent, if both runs have a timing for this part.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn change_percent(&self) -> Option<f64> {
+        let stored = self.stored?.as_nanos() as f64;
+        let current = self.current?.as_nanos() as f64;
+        if stored == 0.0 {
+            return None;
+        }
+        Some((current - stored) / stored * 100.0)
+    }
+
+    /// A part regressed if it got slower by more than `threshold` percent, or if it has a stored timing but no longer
+    /// produced one, e.g. because it failed or timed out.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        if self.stored.is_some() && self.current.is_none() {
+            return true;
+        }
+
+        self.change_percent()
+            .is_some_and(|change| change > threshold)
+    }
+}
+
+/// Pair every part of the `current` timings, and their parse step, with its stored counterpart.
+/// The parse row is left out for days that don't have a parse step in either run.
+pub fn compare(stored: &Timings, current: &Timings) -> Vec<PartDelta> {
+    let mut deltas = vec![];
+
+    for timing in &current.data {
+        let previous = stored.data.iter().find(|t| t.day == timing.day);
+
+        for part in [PARSE_PART, 1, 2] {
+            let median = |t: &Timing| part_stats(t, part).map(|stats| stats.median);
+            let stored = previous.and_then(median);
+            let current = median(timing);
+
+            if part == PARSE_PART && stored.is_none() && current.is_none() {
+                continue;
+            }
+
+            deltas.push(PartDelta {
+                day: timing.day,
+                part,
+                stored,
+                current,
+            });
+        }
+    }
+
+    deltas
+}
+
+fn part_stats(timing: &Timing, part: u8) -> Option<&Stats> {
+    match part {
+        PARSE_PART => timing.parse.as_ref(),
+        1 => timing.part_1.as_ref(),
+        _ => timing.part_2.as_ref(),
+    }
+}
+
+fn format_duration(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".into(), |d| format!("{d:.1?}"))
+}
+
+/// Print a table of per-part deltas, highlighting regressions beyond `threshold`.
+pub fn print_table(deltas: &[PartDelta], threshold: f64) {
+    println!(
+        "{ANSI_BOLD}{:<5} {:<6} {:>12} {:>12} {:>10}{ANSI_RESET}",
+        "Day", "Part", "Stored", "Current", "Change"
+    );
+
+    for delta in deltas {
+        let change = delta
+            .change_percent()
+            .map_or_else(|| "-".into(), |c| format!("{c:+.1}%"));
+
+        let line = format!(
+            "{:<5} {:<6} {:>12} {:>12} {:>10}",
+            delta.day.to_string(),
+            if delta.part == PARSE_PART {
+                "Parse".into()
+            } else {
+                delta.part.to_string()
+            },
+            format_duration(delta.stored),
+            format_duration(delta.current),
+            change
+        );
+
+        if delta.is_regression(threshold) {
+            println!("{ANSI_BOLD}{line} ▲{ANSI_RESET}");
+        } else {
+            println!("{line}");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, PartDelta};
+    use crate::{
+        day,
+        template::{
+            protocol::PARSE_PART,
+            stats::Stats,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn timing(day: crate::template::Day, part_1: Option<u64>, part_2: Option<u64>) -> Timing {
+        parsed_timing(day, None, part_1, part_2)
+    }
+
+    fn parsed_timing(
+        day: crate::template::Day,
+        parse: Option<u64>,
+        part_1: Option<u64>,
+        part_2: Option<u64>,
+    ) -> Timing {
+        let stats = |millis: Option<u64>| millis.map(|m| Stats::single(Duration::from_millis(m)));
+        Timing {
+            day,
+            parse: stats(parse),
+            part_1: stats(part_1),
+            part_2: stats(part_2),
+            total_nanos: 0.0,
+            errors: vec![],
+            timeouts: vec![],
+            memory: vec![],
+        }
+    }
+
+    #[test]
+    fn pairs_stored_and_current_parts() {
+        let stored = Timings {
+            data: vec![timing(day!(1), Some(10), Some(20))],
+        };
+        let current = Timings {
+            data: vec![
+                timing(day!(1), Some(30), None),
+                timing(day!(2), Some(5), None),
+            ],
+        };
+        let deltas = compare(&stored, &current);
+        assert_eq!(deltas.len(), 4);
+        assert_eq!(deltas[0].stored, Some(Duration::from_millis(10)));
+        assert_eq!(deltas[0].current, Some(Duration::from_millis(30)));
+        assert_eq!(deltas[1].current, None);
+        assert_eq!(deltas[2].day, day!(2));
+        assert_eq!(deltas[2].stored, None);
+    }
+
+    #[test]
+    fn computes_relative_change() {
+        let delta = PartDelta {
+            day: day!(1),
+            part: 1,
+            stored: Some(Duration::from_millis(10)),
+            current: Some(Duration::from_millis(30)),
+        };
+        assert_eq!(delta.change_percent(), Some(200.0));
+        assert_eq!(delta.is_regression(10.0), true);
+        assert_eq!(delta.is_regression(250.0), false);
+    }
+
+    #[test]
+    fn ignores_improvements_and_new_parts() {
+        let improved = PartDelta {
+            day: day!(1),
+            part: 1,
+            stored: Some(Duration::from_millis(30)),
+            current: Some(Duration::from_millis(10)),
+        };
+        assert_eq!(improved.is_regression(0.0), false);
+
+        let new = PartDelta {
+            day: day!(1),
+            part: 2,
+            stored: None,
+            current: Some(Duration::from_millis(10)),
+        };
+        assert_eq!(new.change_percent(), None);
+        assert_eq!(new.is_regression(0.0), false);
+    }
+
+    #[test]
+    fn treats_parts_without_a_current_timing_as_regressions() {
+        let missing = PartDelta {
+            day: day!(1),
+            part: 2,
+            stored: Some(Duration::from_millis(10)),
+            current: None,
+        };
+        assert_eq!(missing.change_percent(), None);
+        assert_eq!(missing.is_regression(10.0), true);
+    }
+
+    #[test]
+    fn compares_the_parse_step() {
+        let stored = Timings {
+            data: vec![parsed_timing(day!(1), Some(10), Some(20), Some(30))],
+        };
+        let current = Timings {
+            data: vec![parsed_timing(day!(1), Some(25), Some(20), Some(30))],
+        };
+        let deltas = compare(&stored, &current);
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(deltas[0].part, PARSE_PART);
+        assert_eq!(deltas[0].is_regression(10.0), true);
+        assert_eq!(deltas[1].is_regression(10.0), false);
+    }
+}
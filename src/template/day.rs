@@ -2,13 +2,19 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::{day_count, Year};
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// The number of days of the longest events. How many days an event actually has depends on its year,
+/// see [`Year::day_count`](crate::template::Year::day_count).
+pub const MAX_DAYS: u8 = 25;
+
+/// A valid day number of advent (i.e. an integer in range 1 to [`MAX_DAYS`]).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -22,15 +28,37 @@ const SERVER_UTC_OFFSET: i32 = -5;
 pub struct Day(u8);
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
-    /// returns [`None`] otherwise.
+    /// Creates a [`Day`] from the provided value if it's a day of any event (1 to [`MAX_DAYS`]),
+    /// returns [`None`] otherwise. Use [`Day::new_in`] to check that the day is part of a year's event.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > MAX_DAYS {
             return None;
         }
         Some(Self(day))
     }
 
+    /// Creates a [`Day`] from the provided value if it's part of a year's event,
+    /// returns [`None`] otherwise. A year of [`None`] refers to the default year.
+    pub fn new_in(day: u8, year: Option<Year>) -> Option<Self> {
+        Self::new(day).filter(|_| day <= day_count(year))
+    }
+
+    /// Parses a [`Day`] that is part of a year's event. A year of [`None`] refers to the default year.
+    ///
+    /// ```
+    /// # use advent_of_code::{day, year};
+    /// # use advent_of_code::Day;
+    /// assert_eq!(Day::parse_in("20", Some(year!(2024))).ok(), Some(day!(20)));
+    /// assert_eq!(Day::parse_in("20", Some(year!(2025))).ok(), None);
+    /// ```
+    pub fn parse_in(s: &str, year: Option<Year>) -> Result<Self, DayFromStrError> {
+        let error = DayFromStrError {
+            last: day_count(year),
+        };
+        let day = s.parse().map_err(|_| error)?;
+        Self::new_in(day, year).ok_or(error)
+    }
+
     // Not part of the public API
     #[doc(hidden)]
     pub const fn __new_unchecked(day: u8) -> Self {
@@ -46,10 +74,11 @@ impl Day {
 #[cfg(feature = "today")]
 impl Day {
     /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Whether the day exists in the current event depends on its year.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        if today.month() == 12 && today.day() <= u32::from(MAX_DAYS) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -77,43 +106,55 @@ impl PartialOrd<u8> for Day {
 
 /* -------------------------------------------------------------------------- */
 
+/// Parses a day of the default year's event, see [`Day::parse_in`] for other years.
 impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
+        Self::parse_in(s, None)
     }
 }
 
 /// An error which can be returned when parsing a [`Day`].
-#[derive(Debug)]
-pub struct DayFromStrError;
+#[derive(Debug, Clone, Copy)]
+pub struct DayFromStrError {
+    /// The last day of the event the day was parsed for.
+    last: u8,
+}
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {}", self.last)
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the default year's event, e.g. from the 1st to the 25th.
 pub fn all_days() -> AllDays {
-    AllDays::new()
+    all_days_in(None)
+}
+
+/// An iterator that yields every day of a year's event. A year of [`None`] refers to the default year.
+pub fn all_days_in(year: Option<Year>) -> AllDays {
+    AllDays::new(day_count(year))
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day of an event.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    /// Creates an iterator over the days `1..=last`. `last` is capped at [`MAX_DAYS`].
+    pub fn new(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(MAX_DAYS),
+        }
     }
 }
 
@@ -121,10 +162,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above `MAX_DAYS`.
         let day = Day(self.current);
         self.current += 1;
 
@@ -139,11 +180,11 @@ impl Iterator for AllDays {
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::MAX_DAYS,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a day of advent, i.e. 1 to `MAX_DAYS`"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -154,11 +195,12 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{AllDays, Day};
+    use crate::year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::new(25);
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +229,23 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn shorter_events() {
+        assert_eq!(AllDays::new(12).last(), Some(Day(12)));
+        assert_eq!(AllDays::new(12).count(), 12);
+        assert_eq!(AllDays::new(30).count(), 25);
+    }
+
+    #[test]
+    fn parses_days_of_an_event() {
+        assert_eq!(Day::parse_in("20", Some(year!(2024))).ok(), Some(Day(20)));
+        assert_eq!(Day::parse_in("12", Some(year!(2025))).ok(), Some(Day(12)));
+        assert_eq!(Day::parse_in("0", Some(year!(2024))).ok(), None);
+
+        let error = Day::parse_in("20", Some(year!(2025))).unwrap_err();
+        assert_eq!(error.to_string(), "expecting a day number between 1 and 12");
+    }
 }
 
 /* -------------------------------------------------------------------------- */
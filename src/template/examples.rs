@@ -0,0 +1,405 @@
+/// Example manifests list the example files of a day together with their expected answers, in `data/examples/NN.json`.
+/// A test is generated for every entry (see `build.rs`), and `cargo solve --example` checks against the same expectations.
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::runner::{Answer, ParseOutput, PartOutput};
+use crate::template::{Puzzle, ANSI_BOLD, ANSI_RESET};
+
+/// A single example file and the answers it is expected to produce.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Example {
+    /// File name relative to the examples folder, e.g. `01-2.txt`.
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// Parameters that differ between examples and the puzzle input, e.g. the size of a grid. See [`param`].
+    pub params: HashMap<String, String>,
+}
+
+impl Example {
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            _ => self.part_2.as_deref(),
+        }
+    }
+
+    pub fn read_input(&self, puzzle: Puzzle) -> Result<String, io::Error> {
+        fs::read_to_string(puzzle.data_dir().join("examples").join(&self.file))
+    }
+}
+
+/// Every example of a day.
+#[derive(Clone, Debug, Default)]
+pub struct ExampleManifest {
+    pub examples: Vec<Example>,
+}
+
+impl ExampleManifest {
+    pub fn path(puzzle: Puzzle) -> PathBuf {
+        puzzle
+            .data_dir()
+            .join("examples")
+            .join(format!("{}.json", puzzle.day))
+    }
+
+    /// Read the manifest of a day. Returns [`None`] if the day has no manifest.
+    pub fn read(puzzle: Puzzle) -> Result<Option<Self>, String> {
+        match fs::read_to_string(Self::path(puzzle)) {
+            Ok(s) => ExampleManifest::try_from(s.as_str()).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Write the manifest of a day, creating the examples folder if it does not exist yet.
+    pub fn store(&self, puzzle: Puzzle) -> Result<(), io::Error> {
+        let path = Self::path(puzzle);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
+        JsonValue::from(self).format_to(&mut file)
+    }
+
+    /// Add an example, replacing the answers of an existing entry for the same file but keeping its parameters.
+    pub fn upsert(&mut self, example: Example) {
+        match self.examples.iter_mut().find(|e| e.file == example.file) {
+            Some(entry) => {
+                entry.part_1 = example.part_1;
+                entry.part_2 = example.part_2;
+            }
+            None => self.examples.push(example),
+        }
+    }
+
+    /// Find the entry of an example file, e.g. `data/examples/01-2.txt`.
+    pub fn find(&self, path: &Path) -> Option<&Example> {
+        let file = path.file_name()?.to_str()?;
+        self.examples.iter().find(|e| e.file == file)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+thread_local! {
+    static PARAMS: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// Read a parameter of the example that is currently running.
+/// Returns [`None`] when running against the puzzle input, so solutions can fall back to the puzzle's value:
+///
+/// ```ignore
+/// let width = param("width").unwrap_or(101);
+/// ```
+pub fn param<T: FromStr>(name: &str) -> Option<T> {
+    PARAMS.with(|params| params.borrow().get(name).and_then(|v| v.parse().ok()))
+}
+
+/// Run `f` with the parameters of an example set.
+pub fn with_params<R>(params: &HashMap<String, String>, f: impl FnOnce() -> R) -> R {
+    PARAMS.with(|p| p.replace(params.clone()));
+    let result = f();
+    PARAMS.with(|p| p.borrow_mut().clear());
+    result
+}
+
+/// The parsed input of a parse function that can fail, panics if the example could not be parsed.
+pub fn expect_parsed<O: ParseOutput>(output: O) -> O::Parsed {
+    output
+        .into_parsed()
+        .unwrap_or_else(|e| panic!("failed to parse the example: {e}"))
+}
+
+/// Run a part against an example of the manifest and assert that it produces the expected answer.
+/// Called by the tests that `build.rs` generates from the manifest.
+pub fn check<O: PartOutput>(puzzle: Puzzle, file: &str, part: u8, func: impl Fn(&str) -> O) {
+    check_labeled(puzzle, file, part, &format!("part {part}"), func);
+}
+
+/// Like [`check`], for one of several variants of a part.
+pub fn check_variant<O: PartOutput>(
+    puzzle: Puzzle,
+    file: &str,
+    part: u8,
+    variant: &str,
+    func: impl Fn(&str) -> O,
+) {
+    check_labeled(
+        puzzle,
+        file,
+        part,
+        &format!("part {part} ({variant})"),
+        func,
+    );
+}
+
+fn check_labeled<O: PartOutput>(
+    puzzle: Puzzle,
+    file: &str,
+    part: u8,
+    label: &str,
+    func: impl Fn(&str) -> O,
+) {
+    let manifest = ExampleManifest::read(puzzle)
+        .unwrap_or_else(|e| panic!("could not read example manifest: {e}"))
+        .expect("example manifest does not exist");
+
+    let example = manifest
+        .examples
+        .iter()
+        .find(|e| e.file == file)
+        .unwrap_or_else(|| panic!("example manifest has no entry for {file}"));
+
+    let input = example
+        .read_input(puzzle)
+        .unwrap_or_else(|e| panic!("could not open example file {file}: {e}"));
+
+    let answer = match with_params(&example.params, || func(&input)).to_answer() {
+        Answer::Solved(answer) => Some(answer),
+        Answer::Unsolved => None,
+        Answer::Failed(e) => panic!("{file}, {label} failed: {e}"),
+    };
+    assert_eq!(answer.as_deref(), example.expected(part), "{file}, {label}");
+}
+
+/// Print whether the answers of a run match the example's expectations. Returns `false` on any mismatch.
+pub(crate) fn print_check(example: &Example, answers: &[(u8, Option<String>)]) -> bool {
+    let mut is_match = true;
+
+    let results: Vec<String> = answers
+        .iter()
+        .filter_map(|(part, answer)| {
+            let expected = example.expected(*part)?;
+            if answer.as_deref() == Some(expected) {
+                Some(format!("part {part} ✔"))
+            } else {
+                is_match = false;
+                Some(format!("part {part} ✖ (expected {expected})"))
+            }
+        })
+        .collect();
+
+    if !results.is_empty() {
+        println!("{ANSI_BOLD}Example:{ANSI_RESET} {}", results.join(", "));
+    }
+
+    is_match
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Answers may be given as strings or as numbers.
+fn answer_from_json(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) => Some(s.clone()),
+        JsonValue::Number(n) if n.fract() == 0.0 => Some(format!("{n:.0}")),
+        JsonValue::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+impl From<&ExampleManifest> for JsonValue {
+    fn from(value: &ExampleManifest) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "examples".into(),
+            JsonValue::Array(value.examples.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Example> for JsonValue {
+    fn from(value: &Example) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("file".into(), JsonValue::String(value.file.clone()));
+
+        if let Some(answer) = &value.part_1 {
+            map.insert("part_1".into(), JsonValue::String(answer.clone()));
+        }
+
+        if let Some(answer) = &value.part_2 {
+            map.insert("part_2".into(), JsonValue::String(answer.clone()));
+        }
+
+        if !value.params.is_empty() {
+            map.insert(
+                "params".into(),
+                JsonValue::Object(
+                    value
+                        .params
+                        .iter()
+                        .map(|(k, v)| (k.clone(), JsonValue::String(v.clone())))
+                        .collect(),
+                ),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for ExampleManifest {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON file."))?;
+
+        let json_examples = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("examples")
+            .ok_or("expected JSON document to have key `examples`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.examples` to be an array.")?;
+
+        Ok(ExampleManifest {
+            examples: json_examples
+                .iter()
+                .map(Example::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.file to be a string.")?;
+
+        let params = match json.get("params") {
+            Some(params) => params
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected example.params to be an object.")?
+                .iter()
+                .map(|(k, v)| {
+                    answer_from_json(v).map(|v| (k.clone(), v)).ok_or(format!(
+                        "Expected example.params.{k} to be a string or number."
+                    ))
+                })
+                .collect::<Result<_, _>>()?,
+            None => HashMap::new(),
+        };
+
+        Ok(Example {
+            file: file.clone(),
+            part_1: json.get("part_1").and_then(answer_from_json),
+            part_2: json.get("part_2").and_then(answer_from_json),
+            params,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashMap, path::Path};
+
+    use super::{param, print_check, with_params, Example, ExampleManifest};
+
+    const MANIFEST: &str = r#"{
+        "examples": [
+            { "file": "01.txt", "part_1": 11, "part_2": "31" },
+            { "file": "01-2.txt", "part_2": 48, "params": { "steps": 6, "name": "a" } }
+        ]
+    }"#;
+
+    #[test]
+    fn parses_manifest() {
+        let manifest = ExampleManifest::try_from(MANIFEST).unwrap();
+        assert_eq!(manifest.examples.len(), 2);
+        assert_eq!(manifest.examples[0].expected(1), Some("11"));
+        assert_eq!(manifest.examples[0].expected(2), Some("31"));
+        assert_eq!(manifest.examples[1].expected(1), None);
+        assert_eq!(manifest.examples[1].params.get("steps"), Some(&"6".into()));
+    }
+
+    #[test]
+    fn finds_entries_by_path() {
+        let manifest = ExampleManifest::try_from(MANIFEST).unwrap();
+        let entry = manifest.find(Path::new("data/examples/01-2.txt")).unwrap();
+        assert_eq!(entry.file, "01-2.txt");
+        assert_eq!(manifest.find(Path::new("data/examples/02.txt")), None);
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        assert_eq!(ExampleManifest::try_from("[]").is_err(), true);
+        assert_eq!(
+            ExampleManifest::try_from(r#"{ "examples": [{ "part_1": 1 }] }"#).is_err(),
+            true
+        );
+    }
+
+    #[test]
+    fn upserts_examples() {
+        let mut manifest = ExampleManifest::try_from(MANIFEST).unwrap();
+        manifest.upsert(Example {
+            file: "01-2.txt".into(),
+            part_1: Some("5".into()),
+            ..Default::default()
+        });
+        manifest.upsert(Example {
+            file: "01-3.txt".into(),
+            ..Default::default()
+        });
+
+        assert_eq!(manifest.examples.len(), 3);
+        assert_eq!(manifest.examples[1].expected(1), Some("5"));
+        assert_eq!(manifest.examples[1].expected(2), None);
+        assert_eq!(manifest.examples[1].params.get("steps"), Some(&"6".into()));
+    }
+
+    #[test]
+    fn serializes_manifest() {
+        let manifest = ExampleManifest::try_from(MANIFEST).unwrap();
+        let json = tinyjson::JsonValue::from(&manifest).stringify().unwrap();
+        let parsed = ExampleManifest::try_from(json.as_str()).unwrap();
+        assert_eq!(parsed.examples, manifest.examples);
+    }
+
+    #[test]
+    fn scopes_params() {
+        let params = HashMap::from([("steps".to_string(), "6".to_string())]);
+        assert_eq!(with_params(&params, || param::<u32>("steps")), Some(6));
+        assert_eq!(param::<u32>("steps"), None);
+    }
+
+    #[test]
+    fn checks_answers() {
+        let example = Example {
+            file: "01.txt".into(),
+            part_1: Some("11".into()),
+            part_2: Some("31".into()),
+            params: HashMap::new(),
+        };
+        assert_eq!(
+            print_check(&example, &[(1, Some("11".into())), (2, Some("31".into()))]),
+            true
+        );
+        assert_eq!(
+            print_check(&example, &[(1, Some("11".into())), (2, None)]),
+            false
+        );
+    }
+}
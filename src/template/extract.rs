@@ -0,0 +1,177 @@
+/// Module that finds example inputs and their answers in a puzzle description that was downloaded as markdown.
+/// Every code block is a candidate, and the emphasized values that follow it are candidates for its answers.
+use std::fs;
+
+use crate::template::Puzzle;
+
+/// A code block of the puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Candidate {
+    /// The part whose description contains the code block.
+    pub part: u8,
+    pub input: String,
+    /// Emphasized values that follow the code block, in the descriptions of part 1 and part 2.
+    pub emphasized: [Vec<String>; 2],
+}
+
+impl Candidate {
+    /// The expected answer of a part, i.e. the last emphasized value that follows the code block.
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.emphasized[usize::from(part - 1)]
+            .last()
+            .map(String::as_str)
+    }
+}
+
+/// Collect the code blocks of a puzzle description.
+///
+/// Emphasized values are attributed to the last code block before them. Part 2 usually reuses the example of part 1,
+/// so its values stay with the last block of part 1 until part 2 introduces a block of its own.
+pub fn candidates(markdown: &str) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = vec![];
+    let mut part = 1;
+    let mut block: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        if let Some(lines) = &mut block {
+            if line == "```" {
+                candidates.push(Candidate {
+                    part,
+                    input: lines.iter().map(|l| format!("{l}\n")).collect(),
+                    emphasized: Default::default(),
+                });
+                block = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        if line == "```" {
+            block = Some(vec![]);
+        } else if line.contains("--- Part Two ---") {
+            part = 2;
+        } else if let Some(candidate) = candidates.last_mut() {
+            candidate.emphasized[usize::from(part - 1)].extend(emphasized_values(line));
+        }
+    }
+
+    candidates
+}
+
+/// The title of a puzzle, e.g. `Day 1: Historian Hysteria`.
+pub fn title(markdown: &str) -> Option<String> {
+    let heading = markdown
+        .lines()
+        .find(|line| line.trim_start_matches('\\').starts_with("--- Day "))?;
+    let title = heading
+        .trim_start_matches('\\')
+        .trim_matches(|c| c == '-' || c == ' ');
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// The title of a puzzle, if its description was downloaded.
+pub fn read_title(puzzle: Puzzle) -> Option<String> {
+    let path = puzzle
+        .data_dir()
+        .join("puzzles")
+        .join(format!("{}.md", puzzle.day));
+    title(&fs::read_to_string(path).ok()?)
+}
+
+/// Inline code that is emphasized, i.e. `` `*11*` `` or `` *`11`* ``.
+fn emphasized_values(line: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find('`') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('`') else {
+            break;
+        };
+
+        let code = &after[..end];
+        let before = &rest[..start];
+        let is_emphasized = (code.len() > 2 && code.starts_with('*') && code.ends_with('*'))
+            || (before.ends_with('*')
+                && !before.ends_with("\\*")
+                && after[end + 1..].starts_with('*'));
+
+        if is_emphasized {
+            values.push(code.trim_matches('*').replace("\\*", "*"));
+        }
+
+        rest = &after[end + 1..];
+    }
+
+    values
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{candidates, emphasized_values, title};
+
+    const PUZZLE: &str = "\\--- Day 1: Historian Hysteria ---
+----------
+
+For example:
+
+```
+3   4
+4   3
+```
+
+The distances add up to `2 + 1 + 0` = `*11*`.
+
+\\--- Part Two ---
+----------
+
+The similarity score is *`31`*.
+
+A second example, with a \\* in `*not emphasized`:
+
+```
+1 2
+```
+
+Here, the answer would be `*48*`.
+
+Your puzzle answer was `42`.";
+
+    #[test]
+    fn finds_title() {
+        assert_eq!(title(PUZZLE).as_deref(), Some("Day 1: Historian Hysteria"));
+        assert_eq!(title("For example:"), None);
+    }
+
+    #[test]
+    fn finds_emphasized_values() {
+        assert_eq!(emphasized_values("is `*11*`."), vec!["11"]);
+        assert_eq!(
+            emphasized_values("is *`31`* or `*a\\*b*`"),
+            vec!["31", "a*b"]
+        );
+        assert_eq!(emphasized_values("`1` \\*`2` `*`"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn collects_code_blocks() {
+        let candidates = candidates(PUZZLE);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].part, 1);
+        assert_eq!(candidates[0].input, "3   4\n4   3\n");
+        assert_eq!(candidates[1].part, 2);
+        assert_eq!(candidates[1].input, "1 2\n");
+    }
+
+    #[test]
+    fn attributes_answers_to_blocks() {
+        let candidates = candidates(PUZZLE);
+        assert_eq!(candidates[0].answer(1), Some("11"));
+        assert_eq!(candidates[0].answer(2), Some("31"));
+        assert_eq!(candidates[1].answer(1), None);
+        assert_eq!(candidates[1].answer(2), Some("48"));
+    }
+}
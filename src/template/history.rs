@@ -0,0 +1,360 @@
+/// Append-only history of benchmark runs, stored as one JSON document per line.
+/// Every entry records the timings of a run together with metadata about the revision and machine it ran on.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::protocol::PARSE_PART;
+use crate::template::stats::Stats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{data_dir, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+static HISTORY_FILE_NAME: &str = "timings_history.jsonl";
+
+/// Describes where and when a benchmark run happened.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunMetadata {
+    /// UTC timestamp in ISO 8601 format.
+    pub date: String,
+    /// Short git revision, suffixed with `-dirty` if the work tree had changes.
+    pub revision: Option<String>,
+    pub rustc: Option<String>,
+    pub cpu: Option<String>,
+}
+
+impl RunMetadata {
+    /// Collect metadata about the current revision and machine.
+    pub fn collect() -> Self {
+        Self {
+            date: now(),
+            revision: git_revision(),
+            rustc: command_output("rustc", &["--version"]),
+            cpu: cpu_model(),
+        }
+    }
+}
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub metadata: RunMetadata,
+    pub timings: Timings,
+}
+
+/// All benchmark runs, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Append a run to the history file of a year.
+    pub fn append(entry: &HistoryEntry, year: Option<Year>) -> Result<(), Error> {
+        let line = JsonValue::from(entry)
+            .stringify()
+            .map_err(|e| Error::other(e.to_string()))?;
+
+        let dir = data_dir(year);
+        fs::create_dir_all(&dir)?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(HISTORY_FILE_NAME))?;
+
+        writeln!(file, "{line}")
+    }
+
+    /// Rehydrate the history of a year from its file. Malformed lines are skipped, a missing file yields an empty history.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        fs::read_to_string(data_dir(year).join(HISTORY_FILE_NAME))
+            .map(|s| History::from(s.as_str()))
+            .unwrap_or_default()
+    }
+
+    /// Timings of a single day across all runs that benched it, oldest first.
+    pub fn for_day(&self, day: Day) -> Vec<(&RunMetadata, &Timing)> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let timing = entry.timings.data.iter().find(|t| t.day == day)?;
+                Some((&entry.metadata, timing))
+            })
+            .collect()
+    }
+}
+
+/// Print how the parts of a day evolved over time.
+pub fn print_day_history(history: &History, day: Day) {
+    let runs = history.for_day(day);
+
+    if runs.is_empty() {
+        println!("No benchmark history for day {day}.");
+        return;
+    }
+
+    let format_part = |timing: &Timing, stats: Option<&Stats>, part: u8| match stats {
+        Some(stats) => format!("{:.1?}", stats.median),
+        None if timing.is_timed_out(part) => "TIMEOUT".into(),
+        None => "-".into(),
+    };
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!(
+        "{ANSI_BOLD}{:<21} {:<16} {:>10} {:>10} {:>10}  Toolchain / CPU{ANSI_RESET}",
+        "Date", "Revision", "Parse", "Part 1", "Part 2"
+    );
+
+    for (metadata, timing) in runs {
+        println!(
+            "{:<21} {:<16} {:>10} {:>10} {:>10}  {} / {}",
+            metadata.date,
+            metadata.revision.as_deref().unwrap_or("-"),
+            format_part(timing, timing.parse.as_ref(), PARSE_PART),
+            format_part(timing, timing.part_1.as_ref(), 1),
+            format_part(timing, timing.part_2.as_ref(), 2),
+            metadata.rustc.as_deref().unwrap_or("-"),
+            metadata.cpu.as_deref().unwrap_or("-"),
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let trimmed = stdout.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+fn git_revision() -> Option<String> {
+    let revision = command_output("git", &["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = command_output("git", &["status", "--porcelain"]).is_some();
+    Some(if is_dirty {
+        format!("{revision}-dirty")
+    } else {
+        revision
+    })
+}
+
+fn cpu_model() -> Option<String> {
+    if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+        return cpuinfo
+            .lines()
+            .find(|l| l.starts_with("model name"))
+            .and_then(|l| l.split(':').nth(1))
+            .map(|model| model.trim().to_string());
+    }
+
+    command_output("sysctl", &["-n", "machdep.cpu.brand_string"])
+        .or_else(|| std::env::var("PROCESSOR_IDENTIFIER").ok())
+}
+
+/// The current time as an ISO 8601 UTC timestamp.
+pub fn now() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    format_timestamp(secs)
+}
+
+/// Format seconds since the unix epoch as an ISO 8601 UTC timestamp.
+fn format_timestamp(secs: u64) -> String {
+    let days = secs / 86_400;
+    let rem = secs % 86_400;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// Converts days since the unix epoch to a (year, month, day) date.
+/// see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&str> for History {
+    fn from(value: &str) -> Self {
+        let entries = value
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| JsonValue::from_str(l).ok())
+            .filter_map(|json| HistoryEntry::try_from(&json).ok())
+            .collect();
+
+        History { entries }
+    }
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let optional = |v: &Option<String>| v.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert(
+            "date".into(),
+            JsonValue::String(value.metadata.date.clone()),
+        );
+        map.insert("revision".into(), optional(&value.metadata.revision));
+        map.insert("rustc".into(), optional(&value.metadata.rustc));
+        map.insert("cpu".into(), optional(&value.metadata.cpu));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let date = json
+            .get("date")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.date to be a string.")?;
+
+        let optional = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected entry.data to be an array.")?;
+
+        Ok(HistoryEntry {
+            metadata: RunMetadata {
+                date: date.clone(),
+                revision: optional("revision"),
+                rustc: optional("rustc"),
+                cpu: optional("cpu"),
+            },
+            timings: Timings {
+                data: data
+                    .iter()
+                    .map(Timing::try_from)
+                    .collect::<Result<_, _>>()?,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{format_timestamp, History, HistoryEntry, RunMetadata};
+    use crate::{
+        day,
+        template::{
+            stats::Stats,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn entry(date: &str, days: &[(crate::template::Day, u64)]) -> HistoryEntry {
+        HistoryEntry {
+            metadata: RunMetadata {
+                date: date.into(),
+                revision: Some("abc1234".into()),
+                rustc: Some("rustc 1.83.0".into()),
+                cpu: None,
+            },
+            timings: Timings {
+                data: days
+                    .iter()
+                    .map(|(day, millis)| Timing {
+                        day: *day,
+                        parse: None,
+                        part_1: Some(Stats::single(Duration::from_millis(*millis))),
+                        part_2: None,
+                        total_nanos: 0.0,
+                        errors: vec![],
+                        timeouts: vec![],
+                        memory: vec![],
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp(1_733_404_272), "2024-12-05T13:11:12Z");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn round_trips_entries() {
+        let original = entry("2024-12-05T13:11:12Z", &[(day!(1), 10)]);
+        let line = JsonValue::from(&original).stringify().unwrap();
+        let history = History::from(line.as_str());
+        assert_eq!(history.entries.len(), 1);
+        let parsed = &history.entries[0];
+        assert_eq!(parsed.metadata, original.metadata);
+        assert_eq!(
+            parsed.timings.data[0].part_1,
+            original.timings.data[0].part_1
+        );
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let valid = JsonValue::from(&entry("2024-12-05T13:11:12Z", &[(day!(1), 10)]))
+            .stringify()
+            .unwrap();
+        let history = History::from(format!("{valid}\nnot json\n\n{{}}\n{valid}\n").as_str());
+        assert_eq!(history.entries.len(), 2);
+    }
+
+    #[test]
+    fn filters_runs_by_day() {
+        let history = History {
+            entries: vec![
+                entry("2024-12-01T00:00:00Z", &[(day!(1), 10)]),
+                entry("2024-12-02T00:00:00Z", &[(day!(2), 20)]),
+                entry("2024-12-03T00:00:00Z", &[(day!(1), 5), (day!(2), 15)]),
+            ],
+        };
+        let runs = history.for_day(day!(1));
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].0.date, "2024-12-01T00:00:00Z");
+        assert_eq!(runs[1].1.part_1.unwrap().median, Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,303 @@
+/// Module that converts Advent of Code puzzle pages to markdown.
+/// Only the handful of tags that are used on puzzle pages are supported, everything else is dropped.
+///
+/// Extract the puzzle description and any already accepted answers from a puzzle page and convert them to markdown.
+pub fn puzzle_to_markdown(page: &str) -> String {
+    let fragments: Vec<&str> = find_elements(page, "article")
+        .into_iter()
+        .chain(
+            find_elements(page, "p")
+                .into_iter()
+                .filter(|p| p.contains("Your puzzle answer was")),
+        )
+        .collect();
+
+    // restore document order, articles and answers are interleaved.
+    let mut ordered: Vec<(usize, &str)> = fragments
+        .into_iter()
+        .map(|f| (f.as_ptr() as usize - page.as_ptr() as usize, f))
+        .collect();
+    ordered.sort_unstable_by_key(|(pos, _)| *pos);
+
+    let html: String = ordered.into_iter().map(|(_, f)| f).collect();
+    to_markdown(&html)
+}
+
+/// Extract the message of an answer response page (the content of its `<article>`) as markdown.
+pub fn response_to_markdown(page: &str) -> String {
+    let articles = find_elements(page, "article");
+    if articles.is_empty() {
+        to_markdown(page)
+    } else {
+        to_markdown(&articles.concat())
+    }
+}
+
+/// Find every element with the given tag name, including the tags themselves. Does not handle nesting of the same tag.
+fn find_elements<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+    let mut elements = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(&open) {
+        let candidate = &rest[start..];
+        // make sure that e.g. `<p` does not match `<pre`.
+        let is_tag = candidate[open.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c == '>' || c.is_whitespace());
+
+        if !is_tag {
+            rest = &candidate[open.len()..];
+            continue;
+        }
+
+        let Some(end) = candidate.find(&close) else {
+            break;
+        };
+
+        elements.push(&candidate[..end + close.len()]);
+        rest = &candidate[end + close.len()..];
+    }
+
+    elements
+}
+
+/// Convert a HTML fragment to markdown.
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut heading_start: Option<usize> = None;
+    let mut links: Vec<String> = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => heading_start = Some(out.len()),
+            ("h2", true) => {
+                if let Some(start) = heading_start.take() {
+                    if out[start..].starts_with('-') {
+                        out.insert(start, '\\');
+                    }
+                }
+                out.push_str("\n----------\n\n");
+            }
+            ("p", true) => out.push_str("\n\n"),
+            ("em", _) => out.push('*'),
+            ("code", _) if !in_pre => out.push('`'),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("ul", true) => out.push('\n'),
+            ("li", false) => out.push_str("* "),
+            ("li", true) => out.push('\n'),
+            ("br", _) => out.push('\n'),
+            ("a", false) => {
+                out.push('[');
+                links.push(attribute(tag, "href").unwrap_or_default());
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    collapse_blank_lines(out.trim())
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let decoded = decode_entities(text);
+    if in_pre {
+        out.push_str(&decoded);
+    } else {
+        let text = decoded.replace('\n', " ").replace('*', "\\*");
+        // drop whitespace between block elements.
+        if text.trim().is_empty() && (out.is_empty() || out.ends_with('\n')) {
+            return;
+        }
+        out.push_str(&text);
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+
+        let decoded = entity.and_then(|(name, end)| {
+            let c = match name {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                _ => {
+                    let code = if let Some(hex) = name.strip_prefix("#x") {
+                        u32::from_str_radix(hex, 16).ok()
+                    } else {
+                        name.strip_prefix('#').and_then(|dec| dec.parse().ok())
+                    };
+                    char::from_u32(code?)?
+                }
+            };
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn collapse_blank_lines(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut newlines = 0;
+
+    for c in s.chars() {
+        if c == '\n' {
+            newlines += 1;
+            if newlines > 2 {
+                continue;
+            }
+        } else {
+            newlines = 0;
+        }
+        out.push(c);
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, puzzle_to_markdown, response_to_markdown, to_markdown};
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &amp; &#39;c&#x27; &unknown"),
+            "a <b> & 'c' &unknown"
+        );
+    }
+
+    #[test]
+    fn converts_inline_elements() {
+        let html = r#"<p>The answer is <code><em>11</em></code>, see <a href="/2024/day/1">day 1</a>.</p>"#;
+        assert_eq!(
+            to_markdown(html),
+            "The answer is `*11*`, see [day 1](/2024/day/1)."
+        );
+    }
+
+    #[test]
+    fn converts_block_elements() {
+        let html = "<h2>--- Day 2: Red-Nosed Reports ---</h2><p>For example:</p>\n<pre><code>7 6 4\n1 2 7\n</code></pre>\n<ul>\n<li>one</li>\n<li><em>two</em></li>\n</ul>";
+        let expected = [
+            "\\--- Day 2: Red-Nosed Reports ---",
+            "----------",
+            "",
+            "For example:",
+            "",
+            "```",
+            "7 6 4",
+            "1 2 7",
+            "```",
+            "",
+            "* one",
+            "* *two*",
+        ]
+        .join("\n");
+        assert_eq!(to_markdown(html), expected);
+    }
+
+    #[test]
+    fn extracts_puzzle_description_and_answers() {
+        let page = r#"<html><body><header><p>Ignored</p></header><main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Part one.</p></article>
+<p>Your puzzle answer was <code>42</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Part two.</p></article>
+<form><p>Answer: <input/></p></form>
+</main></body></html>"#;
+        let expected = [
+            "\\--- Day 1: Test ---",
+            "----------",
+            "",
+            "Part one.",
+            "",
+            "Your puzzle answer was `42`.",
+            "",
+            "\\--- Part Two ---",
+            "----------",
+            "",
+            "Part two.",
+        ]
+        .join("\n");
+        assert_eq!(puzzle_to_markdown(page), expected);
+    }
+
+    #[test]
+    fn extracts_response_message() {
+        let page = "<main>\n<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article>\n</main>";
+        assert_eq!(
+            response_to_markdown(page),
+            "That's the right answer!  You are one gold star closer."
+        );
+    }
+}
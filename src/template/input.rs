@@ -0,0 +1,150 @@
+/// Selects the input a solution runs against.
+/// By default, solutions read their puzzle input. `--input <path>` reads a file instead, `--input -` reads stdin,
+/// and `--example [N]` reads an example file.
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::template::Puzzle;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/inputs`.
+    Puzzle,
+    /// An example in `data/examples`, optionally with a part suffix, e.g. `01-2.txt`.
+    Example(Option<u8>),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Parse the input source from the arguments of the current process.
+    /// Returns an error if `--input` is passed without a path.
+    pub fn from_args() -> Result<Self, String> {
+        let args: Vec<String> = env::args().collect();
+        Self::parse(&args)
+    }
+
+    fn parse(args: &[String]) -> Result<Self, String> {
+        if let Some(pos) = args.iter().position(|x| x == "--input") {
+            return match args.get(pos + 1).map(String::as_str) {
+                Some("-") => Ok(InputSource::Stdin),
+                Some(path) if !path.starts_with("--") => Ok(InputSource::File(PathBuf::from(path))),
+                _ => Err("`--input` expects a path, or `-` to read stdin.".into()),
+            };
+        }
+
+        if let Some(pos) = args.iter().position(|x| x == "--example") {
+            let part = args.get(pos + 1).and_then(|x| x.parse().ok());
+            return Ok(InputSource::Example(part));
+        }
+
+        Ok(InputSource::Puzzle)
+    }
+
+    /// Whether answers computed from this source are answers to the puzzle, i.e. can be submitted.
+    pub fn is_puzzle_input(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+
+    /// Path of the file this source reads for a puzzle. Returns [`None`] for stdin.
+    pub fn path(&self, puzzle: Puzzle) -> Option<PathBuf> {
+        let data_file = |folder: &str, name: String| puzzle.data_dir().join(folder).join(name);
+        let day = puzzle.day;
+
+        match self {
+            InputSource::Puzzle => Some(data_file("inputs", format!("{day}.txt"))),
+            InputSource::Example(None) => Some(data_file("examples", format!("{day}.txt"))),
+            InputSource::Example(Some(part)) => {
+                Some(data_file("examples", format!("{day}-{part}.txt")))
+            }
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Read the input of a puzzle from this source.
+    pub fn read(&self, puzzle: Puzzle) -> Result<String, io::Error> {
+        match self.path(puzzle) {
+            Some(path) => fs::read_to_string(path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Describe this source for a puzzle, e.g. `data/inputs/01.txt`.
+    pub fn describe(&self, puzzle: Puzzle) -> String {
+        match self.path(puzzle) {
+            Some(path) => path.display().to_string(),
+            None => "stdin".into(),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::InputSource;
+    use crate::{day, template::Puzzle};
+
+    fn parse(args: &[&str]) -> InputSource {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        InputSource::parse(&args).unwrap()
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(parse(&["01"]), InputSource::Puzzle);
+        assert_eq!(
+            parse(&["01", "--input", "big.txt"]),
+            InputSource::File(PathBuf::from("big.txt"))
+        );
+        assert_eq!(parse(&["01", "--input", "-"]), InputSource::Stdin);
+        assert_eq!(parse(&["01", "--example"]), InputSource::Example(None));
+        assert_eq!(
+            parse(&["01", "--example", "--time"]),
+            InputSource::Example(None)
+        );
+        assert_eq!(
+            parse(&["01", "--example", "2"]),
+            InputSource::Example(Some(2))
+        );
+    }
+
+    #[test]
+    fn rejects_input_without_path() {
+        let args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert!(InputSource::parse(&args(&["01", "--input"])).is_err());
+        assert!(InputSource::parse(&args(&["01", "--input", "--time"])).is_err());
+    }
+
+    #[test]
+    fn resolves_paths() {
+        let puzzle = Puzzle::from(day!(9));
+        assert_eq!(
+            InputSource::Puzzle.path(puzzle),
+            Some(PathBuf::from("data/inputs/09.txt"))
+        );
+        assert_eq!(
+            InputSource::Example(Some(2)).path(puzzle),
+            Some(PathBuf::from("data/examples/09-2.txt"))
+        );
+        assert_eq!(InputSource::Stdin.path(puzzle), None);
+        assert_eq!(InputSource::Stdin.describe(puzzle), "stdin");
+    }
+
+    #[test]
+    fn only_submits_puzzle_input() {
+        assert_eq!(InputSource::Puzzle.is_puzzle_input(), true);
+        assert_eq!(InputSource::Example(None).is_puzzle_input(), false);
+        assert_eq!(InputSource::Stdin.is_puzzle_input(), false);
+    }
+}
@@ -1,68 +1,199 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod compare;
+pub mod examples;
 pub mod runner;
 
 pub use day::*;
+pub use puzzle::*;
+pub use registry::*;
+pub use run_multi::Timeouts;
+pub use year::*;
 
+mod answers;
 mod day;
+mod extract;
+mod history;
+mod html;
+mod input;
+mod memory;
+mod protocol;
+mod puzzle;
 mod readme_benchmarks;
+mod readme_stars;
+mod registry;
+mod report;
 mod run_multi;
+mod stats;
 mod timings;
+mod variants;
+mod verify;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Accepts a [`Day`] of the default year or a [`Puzzle`].
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: impl Into<Puzzle>) -> String {
+    let puzzle = puzzle.into();
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(puzzle.data_dir())
+        .join(folder)
+        .join(format!("{}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<Puzzle>, part: u8) -> String {
+    let puzzle = puzzle.into();
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(puzzle.data_dir())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// The parts are registered in the constant `SOLUTION`, which is picked up by the aggregate `all` binary.
+/// In test builds, a test is included for every entry of the day's example manifest, see [`template::examples`](crate::template::examples).
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Solutions of a year other than the default year are prefixed with it, e.g. `solution!(year = 2023, 1)`.
+///
+/// Solutions that parse their input once for both parts name their parse function last, e.g. `solution!(1, parse = parse)`.
+/// Its output is passed to both parts by reference, and parsing is timed as its own row.
+/// A parse function that can fail returns an [`Option`] or a [`Result`] and is named with `try_parse` instead, e.g. `solution!(1, try_parse = parse)`:
+/// its error is reported on the parse row, and the parts are skipped.
+///
+/// A part can have several implementations, which are named in a module of the part's name, e.g. `solution!(1, part_two = [naive, fast])` runs `part_two::naive` and `part_two::fast`.
+/// All variants are run and their answers are cross-checked. The first variant is the one that is submitted and benchmarked by `cargo time`;
+/// `cargo all` and `cargo time` run the other variants once and report a disagreement as an error of the part.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    // NOTE: the year arms need to come first, `year = 2023` is a valid expression for `$day`.
+    (year = $year:expr, $day:expr $(, parse = $parse:ident)? $(, try_parse = $try_parse:ident)? $(, part_one = [$($v1:ident),+ $(,)?])? $(, part_two = [$($v2:ident),+ $(,)?])?) => {
+        $crate::solution!(@impl ::core::option::Option::Some($crate::year!($year)), $day, [$(parse $parse)? $(try_parse $try_parse)?], [1, part_one, [$($($v1),+)?]] [2, part_two, [$($($v2),+)?]]);
+    };
+    (year = $year:expr, $day:expr, 1 $(, parse = $parse:ident)? $(, try_parse = $try_parse:ident)? $(, part_one = [$($v1:ident),+ $(,)?])?) => {
+        $crate::solution!(@impl ::core::option::Option::Some($crate::year!($year)), $day, [$(parse $parse)? $(try_parse $try_parse)?], [1, part_one, [$($($v1),+)?]]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (year = $year:expr, $day:expr, 2 $(, parse = $parse:ident)? $(, try_parse = $try_parse:ident)? $(, part_two = [$($v2:ident),+ $(,)?])?) => {
+        $crate::solution!(@impl ::core::option::Option::Some($crate::year!($year)), $day, [$(parse $parse)? $(try_parse $try_parse)?], [2, part_two, [$($($v2),+)?]]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr $(, parse = $parse:ident)? $(, try_parse = $try_parse:ident)? $(, part_one = [$($v1:ident),+ $(,)?])? $(, part_two = [$($v2:ident),+ $(,)?])?) => {
+        $crate::solution!(@impl ::core::option::Option::None, $day, [$(parse $parse)? $(try_parse $try_parse)?], [1, part_one, [$($($v1),+)?]] [2, part_two, [$($($v2),+)?]]);
+    };
+    ($day:expr, 1 $(, parse = $parse:ident)? $(, try_parse = $try_parse:ident)? $(, part_one = [$($v1:ident),+ $(,)?])?) => {
+        $crate::solution!(@impl ::core::option::Option::None, $day, [$(parse $parse)? $(try_parse $try_parse)?], [1, part_one, [$($($v1),+)?]]);
+    };
+    ($day:expr, 2 $(, parse = $parse:ident)? $(, try_parse = $try_parse:ident)? $(, part_two = [$($v2:ident),+ $(,)?])?) => {
+        $crate::solution!(@impl ::core::option::Option::None, $day, [$(parse $parse)? $(try_parse $try_parse)?], [2, part_two, [$($($v2),+)?]]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $year:expr, $day:expr, $parse:tt, $( [$part:expr, $func:ident, $variants:tt] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        const _ASSERT_DAY_IN_EVENT: () = assert!(
+            $day <= $crate::template::__build_day_count($year),
+            concat!(
+                "invalid day number `",
+                $day,
+                "`, the day is not part of the event of the solution's year, see `AOC_EVENT_DAYS`"
+            ),
+        );
+
+        /// The current puzzle, i.e. the current day of the solution's year.
+        const PUZZLE: $crate::template::Puzzle = $crate::template::Puzzle {
+            year: $year,
+            day: DAY,
+        };
+
+        /// Registers this day's parts with the aggregate `all` binary.
+        pub const SOLUTION: $crate::template::Solution = $crate::template::Solution {
+            puzzle: PUZZLE,
+            run: |input| $crate::solution!(@run input, $parse, $( [$part, $func, $variants] )*),
+        };
+
+        /// Checks every variant of a part against an example, called by the tests that `build.rs` generates.
+        #[cfg(test)]
+        #[allow(dead_code)]
+        fn __check_example(file: &str, part: u8) {
+            $(
+                if part == $part {
+                    return $crate::solution!(@check file, $parse, $part, $func, $variants);
+                }
+            )*
+            panic!("part {part} is not part of this solution.");
+        }
+
+        // tests generated by `build.rs` from the day's example manifest.
+        #[cfg(test)]
+        include!(concat!(env!("OUT_DIR"), "/examples/", env!("CARGO_BIN_NAME"), ".rs"));
 
+        // unused when the day is compiled into the aggregate `all` binary.
+        #[allow(dead_code)]
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            SOLUTION.solve();
         }
     };
+
+    (@run $input:ident, [], $( [$part:expr, $func:ident, $variants:tt] )*) => {
+        vec![$( ($part, $crate::solution!(@part $input, $part, $func, $variants)), )*]
+    };
+    (@run $input:ident, [$kind:ident $parse:ident], $( [$part:expr, $func:ident, $variants:tt] )*) => {{
+        let Some(parsed) = $crate::template::runner::run_parse($crate::solution!(@parse_fn [$kind $parse]), $input) else {
+            // the parts are skipped if the input could not be parsed.
+            return vec![$( ($part, ::core::option::Option::None), )*];
+        };
+        vec![$( ($part, $crate::solution!(@part &parsed, $part, $func, $variants)), )*]
+    }};
+
+    (@parse_fn [parse $parse:ident]) => {
+        |input| ::core::option::Option::Some($parse(input))
+    };
+    (@parse_fn [try_parse $parse:ident]) => {
+        $parse
+    };
+
+    // parts are called through closures, so that a reference to the parsed input can coerce, e.g. `&Vec<T>` to `&[T]`.
+    (@part $input:expr, $part:expr, $func:ident, []) => {
+        $crate::template::runner::run_part(|input| $func(input), $input, PUZZLE, $part)
+    };
+    (@part $input:expr, $part:expr, $func:ident, [$($variant:ident),+]) => {{
+        let mut variants = $crate::template::runner::PartVariants::new(PUZZLE, $part);
+        $( variants.run(stringify!($variant), |input| $func::$variant(input), $input); )+
+        variants.finish()
+    }};
+
+    (@check $file:ident, $parse:tt, $part:expr, $func:ident, []) => {
+        $crate::template::examples::check(PUZZLE, $file, $part, |input| $func(&$crate::solution!(@parsed $parse, input)))
+    };
+    (@check $file:ident, $parse:tt, $part:expr, $func:ident, [$($variant:ident),+]) => {{
+        $( $crate::template::examples::check_variant(PUZZLE, $file, $part, stringify!($variant), |input| $func::$variant(&$crate::solution!(@parsed $parse, input))); )+
+    }};
+
+    // `@parsed` prepares the input of the parts in generated tests.
+    (@parsed [], $input:ident) => {
+        $input
+    };
+    (@parsed [parse $parse:ident], $input:ident) => {
+        $parse($input)
+    };
+    (@parsed [try_parse $parse:ident], $input:ident) => {
+        $crate::template::examples::expect_parsed($parse($input))
+    };
 }
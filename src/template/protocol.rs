@@ -0,0 +1,278 @@
+/// Machine-readable results exchanged between solution binaries and the runner.
+/// With `--format json`, a solution prints one JSON line per part instead of the human-readable output.
+use std::{collections::HashMap, env, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::memory::MemoryStats;
+use crate::template::runner::Answer;
+use crate::template::stats::Stats;
+
+/// Returns whether the current process was asked to emit JSON records via `--format json`.
+pub fn is_json_format() -> bool {
+    let args: Vec<String> = env::args().collect();
+    args.windows(2)
+        .any(|w| w[0] == "--format" && w[1] == "json")
+}
+
+/// Part number of the records that time the parse step of a solution, see [`run_parse`](crate::template::runner::run_parse).
+pub const PARSE_PART: u8 = 0;
+
+/// Part number of the last part of a day.
+pub const LAST_PART: u8 = 2;
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The error the part returned instead of an answer.
+    pub error: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+    /// Benchmark statistics, present if the part was benched.
+    pub stats: Option<Stats>,
+    /// Heap usage of the first execution, absent if allocations are not counted.
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartResult {
+    pub fn is_success(&self) -> bool {
+        self.answer.is_some()
+    }
+
+    pub fn to_answer(&self) -> Answer {
+        Answer::from_parts(self.answer.clone(), self.error.clone())
+    }
+
+    /// Serialize the result to a single JSON line.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("part result should serialize to JSON")
+    }
+}
+
+/// Announces that a part started running, so that the runner knows which part a timeout interrupted.
+pub fn started_line(part: u8) -> String {
+    format!("{{\"started\":{part}}}")
+}
+
+/// The part of a line written by [`started_line`].
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn parse_started(line: &str) -> Option<u8> {
+    let json = JsonValue::from_str(line).ok()?;
+    let part = json
+        .get::<HashMap<String, JsonValue>>()?
+        .get("started")?
+        .get::<f64>()?;
+    Some(*part as u8)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("success".into(), JsonValue::Boolean(value.is_success()));
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "memory".into(),
+            match &value.memory {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part result to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected result.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?;
+
+        // results of solutions that return an `Option` carry no error.
+        let error = json.get("error").and_then(|v| v.get::<String>()).cloned();
+
+        let duration = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| Duration::from_nanos(*v as u64))
+            .ok_or("Expected result.duration_nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected result.samples to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Stats::try_from(v)?),
+        };
+
+        let memory = match json.get("memory") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(MemoryStats::try_from(v)?),
+        };
+
+        Ok(PartResult {
+            part,
+            answer: answer.cloned(),
+            error,
+            duration,
+            samples,
+            stats,
+            memory,
+        })
+    }
+}
+
+impl FromStr for PartResult {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not a valid JSON line."))?;
+        PartResult::try_from(&json)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_started, started_line, PartResult};
+    use crate::template::{memory::MemoryStats, runner::Answer, stats::Stats};
+
+    #[test]
+    fn round_trips_started_lines() {
+        assert_eq!(parse_started(&started_line(2)), Some(2));
+        assert_eq!(parse_started(&started_line(0)), Some(0));
+        assert_eq!(parse_started("Part 1: 42"), None);
+        assert_eq!(parse_started(r#"{ "part": 1 }"#), None);
+    }
+
+    #[test]
+    fn round_trips_results() {
+        let result = PartResult {
+            part: 2,
+            answer: Some("42".into()),
+            error: None,
+            duration: Duration::from_nanos(74_130),
+            samples: 10_000,
+            stats: Stats::from_samples(&[Duration::from_nanos(74_130)], false),
+            memory: Some(MemoryStats {
+                allocations: 3,
+                bytes_allocated: 2048,
+                peak_bytes: 1024,
+            }),
+        };
+        let parsed: PartResult = result.to_json_line().parse().unwrap();
+        assert_eq!(parsed, result);
+        assert_eq!(parsed.is_success(), true);
+    }
+
+    #[test]
+    fn round_trips_answers_with_special_characters() {
+        let result = PartResult {
+            part: 1,
+            answer: Some("@ @ ( ) ms: \"x\"\n#..#\n".into()),
+            error: None,
+            duration: Duration::from_secs(2),
+            samples: 5,
+            stats: None,
+            memory: None,
+        };
+        let line = result.to_json_line();
+        assert_eq!(line.contains('\n'), false);
+        assert_eq!(line.parse::<PartResult>().unwrap(), result);
+    }
+
+    #[test]
+    fn handles_missing_answers() {
+        let line =
+            r#"{ "part": 1, "answer": null, "duration_nanos": 0, "samples": 1, "success": false }"#;
+        let parsed: PartResult = line.parse().unwrap();
+        assert_eq!(parsed.answer, None);
+        assert_eq!(parsed.is_success(), false);
+    }
+
+    #[test]
+    fn round_trips_errors() {
+        let result = PartResult {
+            part: 1,
+            answer: None,
+            error: Some("invalid digit found in string".into()),
+            duration: Duration::from_nanos(10),
+            samples: 1,
+            stats: None,
+            memory: None,
+        };
+        let parsed: PartResult = result.to_json_line().parse().unwrap();
+        assert_eq!(parsed, result);
+        assert_eq!(parsed.is_success(), false);
+        assert_eq!(
+            parsed.to_answer(),
+            Answer::Failed("invalid digit found in string".into())
+        );
+    }
+
+    #[test]
+    fn rejects_other_lines() {
+        assert_eq!(
+            "Part 1: 0 (74.13ns @ 100000 samples)"
+                .parse::<PartResult>()
+                .is_err(),
+            true
+        );
+        assert_eq!(r#"{ "part": 1 }"#.parse::<PartResult>().is_err(), true);
+    }
+}
@@ -0,0 +1,155 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::template::{day_count, is_default_year, Day, Year};
+
+/// Identifies a puzzle by its year and day.
+///
+/// A year of [`None`] refers to the default year (`AOC_YEAR`). Its files keep the unscoped layout,
+/// e.g. `data/inputs/01.txt` and `src/bin/01.rs`, while every other year is scoped by its number,
+/// e.g. `data/2023/inputs/01.txt` and `src/bin/2023-01.rs`.
+///
+/// # Display
+/// This value displays as the name of the puzzle's binary.
+///
+/// ```
+/// # use advent_of_code::{day, year};
+/// # use advent_of_code::template::Puzzle;
+/// let puzzle = Puzzle::new(Some(year!(2015)), day!(8));
+/// assert_eq!(puzzle.to_string(), "2015-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Option<Year>,
+    pub day: Day,
+}
+
+impl Puzzle {
+    /// Creates a [`Puzzle`]. A year equal to the default year is normalized to [`None`].
+    pub fn new(year: Option<Year>, day: Day) -> Self {
+        let year = year.filter(|_| !is_default_year(year));
+        Self { year, day }
+    }
+
+    /// Creates a [`Puzzle`] if the day is part of the year's event, returns [`None`] otherwise.
+    pub fn try_new(year: Option<Year>, day: Day) -> Option<Self> {
+        (day.into_inner() <= day_count(year)).then(|| Self::new(year, day))
+    }
+
+    /// The year of the puzzle, resolving the default year from `AOC_YEAR`.
+    pub fn year(&self) -> Option<Year> {
+        self.year.or_else(Year::from_env)
+    }
+
+    pub fn is_default_year(&self) -> bool {
+        is_default_year(self.year)
+    }
+
+    /// The data directory of the puzzle's year.
+    pub fn data_dir(&self) -> PathBuf {
+        data_dir(self.year)
+    }
+}
+
+impl From<Day> for Puzzle {
+    fn from(day: Day) -> Self {
+        Self { year: None, day }
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) if !self.is_default_year() => write!(f, "{year}-{}", self.day),
+            _ => write!(f, "{}", self.day),
+        }
+    }
+}
+
+/// The data directory of a year: `data` for the default year, `data/<year>` otherwise.
+pub fn data_dir(year: Option<Year>) -> PathBuf {
+    match year {
+        Some(year) if !is_default_year(Some(year)) => PathBuf::from("data").join(year.to_string()),
+        _ => PathBuf::from("data"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Puzzle {
+    type Err = PuzzleFromStrError;
+
+    /// Parses the name of a puzzle's binary, i.e. `01` or `2023-01`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = match s.split_once('-') {
+            Some((year, day)) => (Some(year.parse().map_err(|_| PuzzleFromStrError)?), day),
+            None => (None, s),
+        };
+        let day = Day::parse_in(day, year).map_err(|_| PuzzleFromStrError)?;
+        Ok(Self::new(year, day))
+    }
+}
+
+/// An error which can be returned when parsing a [`Puzzle`].
+#[derive(Debug)]
+pub struct PuzzleFromStrError;
+
+impl Error for PuzzleFromStrError {}
+
+impl Display for PuzzleFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a puzzle in the format `<day>` or `<year>-<day>`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{data_dir, Puzzle};
+    use crate::{day, year};
+
+    #[test]
+    fn scopes_other_years() {
+        let puzzle = Puzzle::new(Some(year!(2015)), day!(3));
+        assert_eq!(puzzle.to_string(), "2015-03");
+        assert_eq!(puzzle.data_dir(), PathBuf::from("data/2015"));
+        assert_eq!(puzzle.year(), Some(year!(2015)));
+    }
+
+    #[test]
+    fn keeps_default_layout() {
+        let puzzle = Puzzle::from(day!(3));
+        assert_eq!(puzzle.to_string(), "03");
+        assert_eq!(puzzle.data_dir(), PathBuf::from("data"));
+        assert_eq!(data_dir(None), PathBuf::from("data"));
+    }
+
+    #[test]
+    fn parses_puzzles() {
+        assert_eq!(
+            "2015-03".parse::<Puzzle>().ok(),
+            Some(Puzzle::new(Some(year!(2015)), day!(3)))
+        );
+        assert_eq!("03".parse::<Puzzle>().ok(), Some(Puzzle::from(day!(3))));
+        assert_eq!("2015-26".parse::<Puzzle>().ok(), None);
+        assert_eq!("2015".parse::<Puzzle>().ok(), None);
+    }
+
+    #[test]
+    fn rejects_days_outside_of_event() {
+        assert_eq!(Puzzle::try_new(Some(year!(2025)), day!(13)), None);
+        assert_eq!(
+            Puzzle::try_new(Some(year!(2025)), day!(12)),
+            Some(Puzzle::new(Some(year!(2025)), day!(12)))
+        );
+        assert_eq!(
+            Puzzle::try_new(Some(year!(2015)), day!(25)),
+            Some(Puzzle::new(Some(year!(2015)), day!(25)))
+        );
+    }
+}
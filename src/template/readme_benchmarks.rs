@@ -1,12 +1,22 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::memory::format_bytes;
+use crate::template::protocol::PARSE_PART;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::stats::Stats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{day_count, extract, Day, Puzzle};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Width of the share bar of a day that took all of the total time.
+const BAR_WIDTH: usize = 20;
+
+/// Partial blocks of the share bar, by eighths.
+const BAR_EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -25,18 +35,118 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// A column of the benchmark table, in addition to the day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Parse,
+    Part1,
+    Part2,
+    /// Peak heap and allocations. Hidden while no day has memory stats.
+    Memory,
+    /// Number of samples of each part.
+    Samples,
+    /// Percentage of the total time.
+    Share,
+    /// Bar that visualizes the percentage of the total time.
+    Bar,
+}
+
+impl Column {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "memory" => Ok(Column::Memory),
+            "samples" => Ok(Column::Samples),
+            "share" => Ok(Column::Share),
+            "bar" => Ok(Column::Bar),
+            _ => Err(format!(
+                "unknown column `{name}`, expected one of parse, part_1, part_2, memory, samples, share, bar."
+            )),
+        }
+    }
+
+    fn headers(self) -> &'static [&'static str] {
+        match self {
+            Column::Parse => &["Parse"],
+            Column::Part1 => &["Part 1"],
+            Column::Part2 => &["Part 2"],
+            Column::Memory => &["Peak Heap", "Allocations"],
+            Column::Samples => &["Samples"],
+            Column::Share => &["Share"],
+            Column::Bar => &["Relative"],
+        }
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    Day,
+    /// The slowest day first.
+    Cost,
+}
+
+/// Which columns the benchmark table shows and how its days are sorted.
+/// Configured through `AOC_README_COLUMNS` and `AOC_README_SORT`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableConfig {
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Parse, Column::Part1, Column::Part2, Column::Memory],
+            sort: SortOrder::Day,
+        }
+    }
+}
+
+impl TableConfig {
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(
+            env::var("AOC_README_COLUMNS").ok().as_deref(),
+            env::var("AOC_README_SORT").ok().as_deref(),
+        )
+    }
+
+    /// Parse a comma-separated list of columns and a sort order. Missing values fall back to the defaults.
+    fn parse(columns: Option<&str>, sort: Option<&str>) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        if let Some(columns) = columns.filter(|c| !c.trim().is_empty()) {
+            config.columns = columns
+                .split(',')
+                .map(|name| Column::parse(name.trim()))
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Some(sort) = sort.map(str::trim).filter(|s| !s.is_empty()) {
+            config.sort = match sort {
+                "day" => SortOrder::Day,
+                "cost" => SortOrder::Cost,
+                _ => {
+                    return Err(format!(
+                        "unknown sort order `{sort}`, expected day or cost."
+                    ))
+                }
+            };
+        }
+
+        Ok(config)
+    }
+}
+
+/// Locate a table between two markers, or at a single marker if the table was not rendered yet.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
@@ -52,26 +162,136 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/// Replace the table at `marker` with `table`, which starts and ends with the marker itself.
+pub fn replace_table(s: &mut String, marker: &str, table: &str) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    s.replace_range(positions.pos_start..positions.pos_end, table);
+    Ok(())
+}
+
+/// Benchmarked parts are represented by their median duration, parts that returned an error by `✖`.
+fn format_part(timing: &Timing, stats: Option<&Stats>, part: u8) -> String {
+    match stats {
+        Some(stats) => format!("{:.1?}", stats.median),
+        None if timing.is_timed_out(part) => "TIMEOUT".into(),
+        None if timing.is_failed(part) => "✖".into(),
+        None => "-".into(),
+    }
+}
+
+/// Memory columns of a day: the highest peak heap of its parts and the allocations of all its parts.
+fn format_memory(timing: &Timing) -> String {
+    if timing.memory.is_empty() {
+        return "`-` | `-`".into();
+    }
+
+    let peak_bytes = timing.memory.iter().map(|(_, m)| m.peak_bytes).max();
+    let allocations: u64 = timing.memory.iter().map(|(_, m)| m.allocations).sum();
+    format!(
+        "`{}` | `{allocations}`",
+        format_bytes(peak_bytes.unwrap_or_default())
+    )
+}
+
+fn format_samples(timing: &Timing) -> String {
+    let samples = |stats: Option<&Stats>| stats.map_or("-".into(), |s| s.samples.to_string());
+    format!(
+        "{} / {}",
+        samples(timing.part_1.as_ref()),
+        samples(timing.part_2.as_ref())
+    )
+}
+
+/// A bar of eighth blocks whose length is proportional to `share`, e.g. `█████▍`.
+/// Days that took any time at all get at least the smallest block.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn format_bar(share: f64) -> String {
+    let eighths = ((share.clamp(0.0, 1.0) * (BAR_WIDTH * 8) as f64).round() as usize)
+        .max(usize::from(share > 0.0));
+
+    let mut bar = "█".repeat(eighths / 8);
+    if eighths % 8 > 0 {
+        bar.push(BAR_EIGHTHS[eighths % 8]);
+    }
+    bar
+}
+
+fn format_cell(column: Column, timing: &Timing, share: f64) -> String {
+    match column {
+        Column::Parse => format!(
+            "`{}`",
+            format_part(timing, timing.parse.as_ref(), PARSE_PART)
+        ),
+        Column::Part1 => format!("`{}`", format_part(timing, timing.part_1.as_ref(), 1)),
+        Column::Part2 => format!("`{}`", format_part(timing, timing.part_2.as_ref(), 2)),
+        Column::Memory => format_memory(timing),
+        Column::Samples => format!("`{}`", format_samples(timing)),
+        Column::Share => format!("`{:.1}%`", share * 100.0),
+        Column::Bar => format_bar(share),
+    }
+}
+
+/// Days link to their solution, named by their puzzle title if its description was downloaded.
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+    title: impl Fn(Day) -> Option<String>,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    // skip stale timings of days that are not part of the default year's event.
+    let mut timings: Vec<Timing> = timings
+        .data
+        .into_iter()
+        .filter(|t| t.day <= day_count(None))
+        .collect();
+
+    if config.sort == SortOrder::Cost {
+        timings.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    // memory columns are only shown once allocations were counted for some day.
+    let has_memory = timings.iter().any(|t| !t.memory.is_empty());
+    let columns: Vec<Column> = config
+        .columns
+        .iter()
+        .copied()
+        .filter(|c| *c != Column::Memory || has_memory)
+        .collect();
+
+    let total_nanos: f64 = timings.iter().map(|t| t.total_nanos).sum();
+
+    let headers: Vec<&str> = columns.iter().flat_map(|c| c.headers()).copied().collect();
+
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| Day | {} |", headers.join(" | ")),
+        format!("| :---: | {}  |", vec![":---:"; headers.len()].join(" | ")),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    for timing in timings {
+        let path = get_path_for_bin(Puzzle::from(timing.day));
+        let name = title(timing.day).unwrap_or_else(|| format!("Day {}", timing.day.into_inner()));
+        let share = if total_nanos > 0.0 {
+            timing.total_nanos / total_nanos
+        } else {
+            0.0
+        };
+
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|c| format_cell(*c, &timing, share))
+            .collect();
+
+        lines.push(format!("| [{name}]({path}) | {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
@@ -81,47 +301,75 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+    title: impl Fn(Day) -> Option<String>,
+) -> Result<(), Error> {
+    let table = construct_table("##", timings, total_millis, config, title);
+    replace_table(s, MARKER, &table)
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
+    let config = TableConfig::from_env().map_err(Error::Parser)?;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, &config, |day| {
+        extract::read_title(Puzzle::from(day))
+    })?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use std::time::Duration;
+
+    use super::{format_bar, update_content, Column, SortOrder, TableConfig, MARKER};
+    use crate::{
+        day, template::memory::MemoryStats, template::stats::Stats, template::timings::Timing,
+        template::timings::Timings,
+    };
+
+    fn stats(millis: u64) -> Stats {
+        Stats::single(Duration::from_millis(millis))
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: Some(stats(5)),
+                    part_1: Some(stats(10)),
+                    part_2: Some(stats(20)),
                     total_nanos: 3e+10,
+                    errors: vec![],
+                    timeouts: vec![],
+                    memory: vec![],
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(stats(30)),
+                    part_2: Some(stats(40)),
                     total_nanos: 7e+10,
+                    errors: vec![],
+                    timeouts: vec![],
+                    memory: vec![],
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    parse: None,
+                    part_1: Some(stats(40)),
+                    part_2: None,
+                    total_nanos: 4e+10,
+                    errors: vec![],
+                    timeouts: vec![2],
+                    memory: vec![],
                 },
             ],
         }
@@ -131,28 +379,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            |_| None,
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            |_| None,
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            |_| None,
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            |_| None,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            |_| None,
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,18 +443,25 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            |_| None,
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `5.0ms` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `TIMEOUT` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +470,93 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].memory = vec![
+            (
+                1,
+                MemoryStats {
+                    allocations: 10,
+                    bytes_allocated: 4096,
+                    peak_bytes: 2048,
+                },
+            ),
+            (
+                2,
+                MemoryStats {
+                    allocations: 2,
+                    bytes_allocated: 512,
+                    peak_bytes: 512,
+                },
+            ),
+        ];
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default(), |_| None).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[3],
+            "| Day | Parse | Part 1 | Part 2 | Peak Heap | Allocations |"
+        );
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/01.rs) | `5.0ms` | `10.0ms` | `20.0ms` | `2.0 KiB` | `12` |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/02.rs) | `-` | `30.0ms` | `40.0ms` | `-` | `-` |"
+        );
+    }
+
+    #[test]
+    fn parses_table_config() {
+        assert_eq!(TableConfig::parse(None, None), Ok(TableConfig::default()));
+        assert_eq!(
+            TableConfig::parse(Some("part_1, part_2,share"), Some("cost")),
+            Ok(TableConfig {
+                columns: vec![Column::Part1, Column::Part2, Column::Share],
+                sort: SortOrder::Cost,
+            })
+        );
+        assert!(TableConfig::parse(Some("part_3"), None).is_err());
+        assert!(TableConfig::parse(None, Some("name")).is_err());
+    }
+
+    #[test]
+    fn formats_bars() {
+        assert_eq!(format_bar(1.0), "█".repeat(20));
+        assert_eq!(format_bar(0.5), "█".repeat(10));
+        assert_eq!(format_bar(0.0125), "▎");
+        assert_eq!(format_bar(0.3), "██████");
+        assert_eq!(format_bar(0.33), "██████▋");
+        assert_eq!(format_bar(0.0001), "▏");
+        assert_eq!(format_bar(0.0), "");
+    }
+
+    #[test]
+    fn format_configured_columns() {
+        let config = TableConfig {
+            columns: vec![Column::Part2, Column::Samples, Column::Share, Column::Bar],
+            sort: SortOrder::Cost,
+        };
+        let title = |day: crate::template::Day| {
+            (day == day!(2)).then(|| "Day 2: Red-Nosed Reports".to_string())
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &config, title).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[3..8],
+            [
+                "| Day | Part 2 | Samples | Share | Relative |",
+                "| :---: | :---: | :---: | :---: | :---:  |",
+                "| [Day 2: Red-Nosed Reports](./src/bin/02.rs) | `40.0ms` | `1 / 1` | `50.0%` | ██████████ |",
+                "| [Day 4](./src/bin/04.rs) | `TIMEOUT` | `1 / -` | `28.6%` | █████▊ |",
+                "| [Day 1](./src/bin/01.rs) | `20.0ms` | `1 / 1` | `21.4%` | ████▎ |",
+            ]
+        );
+    }
 }
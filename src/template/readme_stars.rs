@@ -0,0 +1,235 @@
+/// Module that updates the readme with the stars of every solved day.
+/// The table mirrors the one `advent-readme-stars` renders, but is built from the local answer ledger.
+use std::fs;
+
+use crate::template::answers::Answers;
+use crate::template::aoc_client::DEFAULT_BASE_URL;
+use crate::template::readme_benchmarks::{replace_table, Error};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::verify::{DayVerdict, Verdict};
+use crate::template::{extract, Day, Puzzle, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+static STAR: &str = "⭐";
+
+/// The stars of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStars {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+    /// Date of the last star of the day, e.g. `2024-12-01`.
+    pub solved: Option<String>,
+}
+
+impl DayStars {
+    pub fn count(&self) -> usize {
+        usize::from(self.part_1) + usize::from(self.part_2)
+    }
+}
+
+/// Collect the stars of `days` from the accepted answers. Days without stars are skipped.
+/// If `verdicts` are passed, a part only earns its star if its solution still produces the accepted answer.
+pub fn collect(
+    answers: &Answers,
+    days: impl Iterator<Item = Day>,
+    verdicts: Option<&[DayVerdict]>,
+) -> Vec<DayStars> {
+    let is_starred = |day: Day, part: u8| {
+        if answers.accepted(day, part).is_none() {
+            return false;
+        }
+
+        let Some(verdicts) = verdicts else {
+            return true;
+        };
+
+        verdicts.iter().filter(|v| v.day == day).any(|v| {
+            let verdict = if part == 1 { &v.part_1 } else { &v.part_2 };
+            *verdict == Verdict::Pass
+        })
+    };
+
+    days.filter_map(|day| {
+        let part_1 = is_starred(day, 1);
+        let part_2 = is_starred(day, 2);
+
+        // ISO dates sort chronologically.
+        let solved = [(1, part_1), (2, part_2)]
+            .into_iter()
+            .filter(|(_, starred)| *starred)
+            .filter_map(|(part, _)| answers.solved_date(day, part))
+            .max()
+            .map(ToString::to_string);
+
+        (part_1 || part_2).then_some(DayStars {
+            day,
+            part_1,
+            part_2,
+            solved,
+        })
+    })
+    .collect()
+}
+
+/// Days link to their puzzle, named by its title if its description was downloaded.
+/// Without a year, days link to their solution instead.
+fn construct_table(
+    year: Option<Year>,
+    stars: &[DayStars],
+    title: impl Fn(Day) -> Option<String>,
+) -> String {
+    let header = match year {
+        Some(year) => format!("## {year} Results"),
+        None => "## Results".into(),
+    };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 | Solved |".into(),
+        "| :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    let star = |starred: bool| if starred { STAR } else { "" };
+
+    for day_stars in stars {
+        let day = day_stars.day;
+        let name = title(day).unwrap_or_else(|| format!("Day {}", day.into_inner()));
+        let link = match year {
+            Some(year) => format!("{DEFAULT_BASE_URL}/{year}/day/{}", day.into_inner()),
+            None => get_path_for_bin(Puzzle::from(day)),
+        };
+
+        lines.push(format!(
+            "| [{name}]({link}) | {} | {} | {} |",
+            star(day_stars.part_1),
+            star(day_stars.part_2),
+            day_stars.solved.as_deref().unwrap_or("-")
+        ));
+    }
+
+    let total: usize = stars.iter().map(DayStars::count).sum();
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {total} {STAR}**"));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+/// The stars table of a year, as it is written to the readme.
+pub fn render(year: Option<Year>, stars: &[DayStars]) -> String {
+    construct_table(year.or_else(Year::from_env), stars, |day| {
+        extract::read_title(Puzzle::new(year, day))
+    })
+}
+
+pub fn update(table: &str) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    replace_table(&mut readme, MARKER, table)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect, construct_table, replace_table, DayStars, MARKER};
+    use crate::{
+        day,
+        template::{
+            answers::Answers,
+            verify::{DayVerdict, Verdict},
+        },
+        year,
+    };
+
+    fn answers() -> Answers {
+        let json = r#"{ "data": [
+            { "day": "01", "part": 1, "answer": "42", "outcome": "correct", "date": "2024-12-01T05:10:00Z" },
+            { "day": "01", "part": 2, "answer": "7", "outcome": "correct", "date": "2024-12-03T06:00:00Z" },
+            { "day": "02", "part": 1, "answer": "11", "outcome": "correct" },
+            { "day": "03", "part": 1, "answer": "1", "outcome": "incorrect" }
+        ] }"#;
+        Answers::try_from(json).unwrap()
+    }
+
+    #[test]
+    fn collects_stars_from_accepted_answers() {
+        let stars = collect(&answers(), [day!(1), day!(2), day!(3)].into_iter(), None);
+        assert_eq!(
+            stars,
+            vec![
+                DayStars {
+                    day: day!(1),
+                    part_1: true,
+                    part_2: true,
+                    solved: Some("2024-12-03".into()),
+                },
+                DayStars {
+                    day: day!(2),
+                    part_1: true,
+                    part_2: false,
+                    solved: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn withholds_stars_of_failing_parts() {
+        let verdicts = [DayVerdict {
+            day: day!(1),
+            part_1: Verdict::Pass,
+            part_2: Verdict::Fail {
+                expected: "7".into(),
+                actual: Some("8".into()),
+            },
+        }];
+        let stars = collect(&answers(), [day!(1), day!(2)].into_iter(), Some(&verdicts));
+        assert_eq!(
+            stars,
+            vec![DayStars {
+                day: day!(1),
+                part_1: true,
+                part_2: false,
+                solved: Some("2024-12-01".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn formats_stars_table() {
+        let stars = collect(&answers(), [day!(1), day!(2)].into_iter(), None);
+        let title = |day: crate::template::Day| {
+            (day == day!(1)).then(|| "Day 1: Historian Hysteria".to_string())
+        };
+
+        let mut s = format!("foo\n{MARKER}\nbar");
+        let table = construct_table(Some(year!(2024)), &stars, title);
+        replace_table(&mut s, MARKER, &table).unwrap();
+        replace_table(&mut s, MARKER, &table).unwrap();
+
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 | Solved |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1: Historian Hysteria](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ | 2024-12-03 |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |  | - |",
+            "",
+            "**Total: 3 ⭐**",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -0,0 +1,105 @@
+/// Registry of solutions that allows running any day in-process.
+/// Each day registers itself through the `SOLUTION` constant created by [`solution!`](crate::solution).
+use std::{env, process};
+
+use crate::template::examples::{self, Example, ExampleManifest};
+use crate::template::input::InputSource;
+use crate::template::protocol::is_json_format;
+use crate::template::{runner, Puzzle, ANSI_ITALIC, ANSI_RESET};
+
+/// The answer of every part that ran, by part number.
+pub type PartAnswers = Vec<(u8, Option<String>)>;
+
+/// A puzzle and the parts it has registered.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: Puzzle,
+    /// Runs every registered part against the provided input and returns the answer of each part.
+    pub run: fn(&str) -> PartAnswers,
+}
+
+impl Solution {
+    /// Reads the input selected on the command line, the puzzle input by default, and runs all registered parts against it.
+    /// Exits with the code of the submission outcome if an answer was submitted and not accepted.
+    ///
+    /// Examples that are listed in the day's manifest run with their parameters and are checked against their expected answers.
+    pub fn solve(&self) {
+        let source = match InputSource::from_args() {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+        let example = self.find_example(&source);
+
+        let input = match source.read(self.puzzle) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Failed to read input {}: {e}", source.describe(self.puzzle));
+                process::exit(1);
+            }
+        };
+
+        if !is_json_format() {
+            println!(
+                "{ANSI_ITALIC}Input: {}{ANSI_RESET}",
+                source.describe(self.puzzle)
+            );
+        }
+
+        let answers = match &example {
+            Some(example) => examples::with_params(&example.params, || (self.run)(&input)),
+            None => (self.run)(&input),
+        };
+
+        if let Some(example) = &example {
+            if !examples::print_check(example, &answers) {
+                process::exit(1);
+            }
+        }
+
+        let code = runner::exit_code();
+        if code != 0 {
+            process::exit(code);
+        }
+    }
+
+    /// The manifest entry of the example selected by `source`, if any.
+    fn find_example(&self, source: &InputSource) -> Option<Example> {
+        if !matches!(source, InputSource::Example(_)) {
+            return None;
+        }
+
+        match ExampleManifest::read(self.puzzle) {
+            Ok(manifest) => manifest?.find(&source.path(self.puzzle)?).cloned(),
+            Err(e) => {
+                eprintln!(
+                    "Failed to read example manifest {}: {e}",
+                    ExampleManifest::path(self.puzzle).display()
+                );
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Entry point of the aggregate binary: runs the puzzle passed as first argument, e.g. `01` or `2023-01`.
+pub fn run_registered(solutions: &[Solution]) {
+    let Some(Ok(puzzle)) = env::args().nth(1).map(|arg| arg.parse::<Puzzle>()) else {
+        eprintln!("Unexpected command-line input. Format: all 01 [--time]");
+        process::exit(1);
+    };
+
+    // normalize the year of solutions that name the default year explicitly.
+    match solutions
+        .iter()
+        .find(|solution| Puzzle::new(solution.puzzle.year, solution.puzzle.day) == puzzle)
+    {
+        Some(solution) => solution.solve(),
+        None => {
+            eprintln!("Puzzle {puzzle} has no registered solution.");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,382 @@
+/// Module that classifies the outcome of running a day, and summarizes the outcomes of running several days.
+use crate::template::protocol::PARSE_PART;
+use crate::template::run_multi::child_commands::SolutionRun;
+use crate::template::verify::{DayVerdict, Verdict};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Exit code of a Rust process that panicked.
+const PANIC_EXIT_CODE: i32 = 101;
+
+/// Number of stderr lines that are kept to explain a failure.
+const EXCERPT_LINES: usize = 5;
+
+/// The outcome of running a single day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayStatus {
+    Ok,
+    /// No part produced an answer, e.g. because the day was scaffolded but not solved yet.
+    Unsolved,
+    NotScaffolded,
+    /// The puzzle input has not been downloaded.
+    NoInput,
+    BuildFailed,
+    Panicked,
+    /// The solution exited with an error, e.g. because its input is missing, or a part returned an error.
+    Failed,
+    /// A part produced an answer that differs from the accepted answer.
+    WrongAnswer,
+    Timeout,
+}
+
+impl DayStatus {
+    pub fn is_failure(self) -> bool {
+        !matches!(
+            self,
+            DayStatus::Ok | DayStatus::Unsolved | DayStatus::NotScaffolded | DayStatus::NoInput
+        )
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            DayStatus::Ok => "✔ ok",
+            DayStatus::Unsolved => "- unsolved",
+            DayStatus::NotScaffolded => "- not scaffolded",
+            DayStatus::NoInput => "- no input",
+            DayStatus::BuildFailed => "✖ build failed",
+            DayStatus::Panicked => "✖ panicked",
+            DayStatus::Failed => "✖ failed",
+            DayStatus::WrongAnswer => "✖ wrong answer",
+            DayStatus::Timeout => "✖ timeout",
+        }
+    }
+}
+
+/// The status of a day, with details that explain a failure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayReport {
+    pub day: Day,
+    pub status: DayStatus,
+    pub details: Vec<String>,
+}
+
+impl DayReport {
+    pub fn new(day: Day, status: DayStatus, details: Vec<String>) -> Self {
+        Self {
+            day,
+            status,
+            details,
+        }
+    }
+
+    /// Classify a finished solution run. Timeouts take precedence over crashes, crashes over wrong answers.
+    pub fn from_run(day: Day, run: &SolutionRun, verdict: &DayVerdict) -> Self {
+        if let Some(part) = run.timed_out {
+            let details = vec![format!("{} exceeded the timeout", part_name(part))];
+            return Self::new(day, DayStatus::Timeout, details);
+        }
+
+        match run.exit_code {
+            Some(0) => {}
+            // with `panic = "abort"`, as in release builds, a panic terminates the process by SIGABRT instead.
+            code if code == Some(PANIC_EXIT_CODE) || has_panicked(&run.stderr) => {
+                return Self::new(day, DayStatus::Panicked, excerpt(&run.stderr));
+            }
+            code => {
+                let mut details = excerpt(&run.stderr);
+                if details.is_empty() {
+                    details.push(code.map_or_else(
+                        || "terminated by a signal".into(),
+                        |code| format!("exited with code {code}"),
+                    ));
+                }
+                return Self::new(day, DayStatus::Failed, details);
+            }
+        }
+
+        let errors: Vec<String> = run
+            .results
+            .iter()
+            .filter_map(|r| Some(format!("{}: {}", part_name(r.part), r.error.as_ref()?)))
+            .collect();
+
+        if !errors.is_empty() {
+            return Self::new(day, DayStatus::Failed, errors);
+        }
+
+        let wrong_answers: Vec<String> = [(1, &verdict.part_1), (2, &verdict.part_2)]
+            .into_iter()
+            .filter_map(|(part, verdict)| match verdict {
+                Verdict::Fail { expected, actual } => Some(format!(
+                    "part {part}: expected {expected}, got {}",
+                    actual.as_deref().unwrap_or("no answer")
+                )),
+                _ => None,
+            })
+            .collect();
+
+        if !wrong_answers.is_empty() {
+            return Self::new(day, DayStatus::WrongAnswer, wrong_answers);
+        }
+
+        if run.results.iter().any(|r| r.answer.is_some()) {
+            Self::new(day, DayStatus::Ok, vec![])
+        } else {
+            Self::new(day, DayStatus::Unsolved, vec![])
+        }
+    }
+}
+
+/// Name of a part in summaries, e.g. `part 1` or `parse`.
+pub fn part_name(part: u8) -> String {
+    if part == PARSE_PART {
+        "parse".into()
+    } else {
+        format!("part {part}")
+    }
+}
+
+/// Whether a solution's stderr contains a panic message, i.e. `thread 'main' panicked at src/bin/01.rs:3:5:`.
+fn has_panicked(stderr: &[String]) -> bool {
+    stderr
+        .iter()
+        .any(|l| l.starts_with("thread '") && l.contains(" panicked at "))
+}
+
+/// The lines of a solution's stderr that explain a failure.
+/// If the solution panicked, this is its panic message followed by its location, otherwise the last lines of its output.
+pub fn excerpt(stderr: &[String]) -> Vec<String> {
+    // hints like `note: run with RUST_BACKTRACE=1` and backtraces do not explain anything.
+    let lines: Vec<&str> = stderr
+        .iter()
+        .map(String::as_str)
+        .take_while(|l| *l != "stack backtrace:")
+        .filter(|l| !l.trim().is_empty() && !l.starts_with("note: "))
+        .collect();
+
+    let panic = lines.iter().enumerate().find_map(|(i, l)| {
+        let (_, location) = l.split_once(" panicked at ")?;
+        Some((i, location.trim_end_matches(':')))
+    });
+
+    match panic {
+        Some((i, location)) => lines[i + 1..]
+            .iter()
+            .take(EXCERPT_LINES - 1)
+            .map(ToString::to_string)
+            .chain([format!("at {location}")])
+            .collect(),
+        None => lines[lines.len().saturating_sub(EXCERPT_LINES)..]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    }
+}
+
+/// The compiler errors in the output of a failed build.
+pub fn build_excerpt(stderr: &str) -> Vec<String> {
+    stderr
+        .lines()
+        .filter(|l| l.starts_with("error") || l.trim_start().starts_with("-->"))
+        .take(EXCERPT_LINES)
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Print a status table of all days, followed by the details of every failed day.
+pub fn print_summary(reports: &[DayReport]) {
+    println!(
+        "{ANSI_BOLD}{:<5} {:<18} Details{ANSI_RESET}",
+        "Day", "Status"
+    );
+
+    for report in reports {
+        let line = format!(
+            "{:<5} {:<18} {}",
+            report.day.to_string(),
+            report.status.label(),
+            report.details.first().map_or("", String::as_str)
+        );
+        let line = line.trim_end();
+
+        if report.status.is_failure() {
+            println!("{ANSI_BOLD}{line}{ANSI_RESET}");
+        } else {
+            println!("{line}");
+        }
+    }
+
+    for report in reports.iter().filter(|r| r.details.len() > 1) {
+        println!("\nDay {}:", report.day);
+        for line in &report.details {
+            println!("  {ANSI_ITALIC}{line}{ANSI_RESET}");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{build_excerpt, excerpt, DayReport, DayStatus};
+    use crate::{
+        day,
+        template::{
+            protocol::PartResult,
+            run_multi::child_commands::SolutionRun,
+            verify::{DayVerdict, Verdict},
+        },
+    };
+
+    fn result(part: u8, answer: Option<&str>, error: Option<&str>) -> PartResult {
+        PartResult {
+            part,
+            answer: answer.map(Into::into),
+            error: error.map(Into::into),
+            duration: Duration::from_nanos(1),
+            samples: 1,
+            stats: None,
+            memory: None,
+        }
+    }
+
+    fn run(results: Vec<PartResult>, exit_code: Option<i32>, stderr: &[&str]) -> SolutionRun {
+        SolutionRun {
+            results,
+            timed_out: None,
+            exit_code,
+            stderr: stderr.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    fn verdict(part_1: Verdict) -> DayVerdict {
+        DayVerdict {
+            day: day!(1),
+            part_1,
+            part_2: Verdict::Missing,
+        }
+    }
+
+    fn status(run: &SolutionRun, part_1: Verdict) -> DayStatus {
+        DayReport::from_run(day!(1), run, &verdict(part_1)).status
+    }
+
+    #[test]
+    fn classifies_successful_runs() {
+        let solved = run(vec![result(1, Some("42"), None)], Some(0), &[]);
+        assert_eq!(status(&solved, Verdict::Pass), DayStatus::Ok);
+        assert_eq!(status(&solved, Verdict::Missing), DayStatus::Ok);
+
+        let unsolved = run(vec![result(1, None, None)], Some(0), &[]);
+        assert_eq!(status(&unsolved, Verdict::Missing), DayStatus::Unsolved);
+    }
+
+    #[test]
+    fn classifies_failed_runs() {
+        let panicked = run(
+            vec![],
+            Some(101),
+            &["thread 'main' panicked at src/bin/01.rs:3:5:"],
+        );
+        assert_eq!(status(&panicked, Verdict::Missing), DayStatus::Panicked);
+
+        // release builds abort on panic, the process is terminated by a signal.
+        let aborted = run(
+            vec![],
+            None,
+            &["thread 'main' panicked at src/bin/18.rs:3:5:", "oops"],
+        );
+        let report = DayReport::from_run(day!(1), &aborted, &verdict(Verdict::Missing));
+        assert_eq!(report.status, DayStatus::Panicked);
+        assert_eq!(
+            report.details,
+            vec!["oops".to_string(), "at src/bin/18.rs:3:5".to_string()]
+        );
+
+        let killed = run(vec![], None, &[]);
+        assert_eq!(status(&killed, Verdict::Missing), DayStatus::Failed);
+
+        let crashed = run(vec![], Some(1), &[]);
+        let report = DayReport::from_run(day!(1), &crashed, &verdict(Verdict::Missing));
+        assert_eq!(report.status, DayStatus::Failed);
+        assert_eq!(report.details, vec!["exited with code 1".to_string()]);
+
+        let errored = run(vec![result(2, None, Some("bad input"))], Some(0), &[]);
+        let report = DayReport::from_run(day!(1), &errored, &verdict(Verdict::Missing));
+        assert_eq!(report.status, DayStatus::Failed);
+        assert_eq!(report.details, vec!["part 2: bad input".to_string()]);
+
+        let wrong = run(vec![result(1, Some("41"), None)], Some(0), &[]);
+        let fail = Verdict::Fail {
+            expected: "42".into(),
+            actual: Some("41".into()),
+        };
+        assert_eq!(status(&wrong, fail), DayStatus::WrongAnswer);
+
+        let mut timed_out = run(vec![result(1, Some("42"), None)], None, &[]);
+        timed_out.timed_out = Some(2);
+        assert_eq!(status(&timed_out, Verdict::Pass), DayStatus::Timeout);
+    }
+
+    #[test]
+    fn excerpts_panic_messages() {
+        let stderr: Vec<String> = [
+            "some debug output",
+            "thread 'main' panicked at src/bin/01.rs:3:5:",
+            "attempt to subtract with overflow",
+            "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+        assert_eq!(
+            excerpt(&stderr),
+            vec![
+                "attempt to subtract with overflow".to_string(),
+                "at src/bin/01.rs:3:5".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn excerpts_skip_backtraces() {
+        let stderr: Vec<String> = [
+            "thread 'main' (1234) panicked at src/bin/01.rs:3:5:",
+            "index out of bounds: the len is 0 but the index is 35",
+            "stack backtrace:",
+            "   0: __rustc::rust_begin_unwind",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+        assert_eq!(
+            excerpt(&stderr),
+            vec![
+                "index out of bounds: the len is 0 but the index is 35".to_string(),
+                "at src/bin/01.rs:3:5".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn excerpts_last_lines() {
+        let stderr: Vec<String> = (1..=8).map(|i| format!("line {i}")).collect();
+        assert_eq!(excerpt(&stderr).first().map(String::as_str), Some("line 4"));
+        assert_eq!(excerpt(&stderr).len(), 5);
+    }
+
+    #[test]
+    fn excerpts_compiler_errors() {
+        let stderr = "   Compiling advent_of_code v0.11.0\nerror[E0425]: cannot find value `x` in this scope\n --> src/bin/01.rs:4:5\n  |\n4 |     x\n";
+        assert_eq!(
+            build_excerpt(stderr),
+            vec![
+                "error[E0425]: cannot find value `x` in this scope".to_string(),
+                " --> src/bin/01.rs:4:5".to_string()
+            ]
+        );
+    }
+}
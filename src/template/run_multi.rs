@@ -1,55 +1,277 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::Answers;
+use crate::template::input::InputSource;
+use crate::template::report::{self, build_excerpt, DayReport, DayStatus};
+use crate::template::verify::verify_day;
+use crate::template::{Day, Puzzle, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
-    all_days,
+    all_days_in,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Wall-clock limits for running a solution. A solution that exceeds one is killed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Limit for all parts of a day together.
+    pub day: Option<Duration>,
+    /// Limit for a single part, including its benchmark.
+    pub part: Option<Duration>,
+}
+
+/// The outcome of running a set of days.
+#[derive(Clone, Debug, Default)]
+pub struct MultiRun {
+    /// Timings of the days that produced results.
+    pub timings: Timings,
+    pub reports: Vec<DayReport>,
+}
+
+impl MultiRun {
+    pub fn has_failures(&self) -> bool {
+        self.reports.iter().any(|r| r.status.is_failure())
+    }
+}
+
+/// Where the output of a day goes.
+pub enum Output {
+    /// Results are discarded, output of the solution on stderr is forwarded as it arrives.
+    Silent,
+    /// Everything is printed as it arrives.
+    Stream,
+    /// Everything is kept until the day finished, so that days that run in parallel do not interleave.
+    Buffer(Vec<OutputLine>),
+}
+
+pub enum OutputLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+impl Output {
+    pub fn println(&mut self, line: String) {
+        match self {
+            Output::Silent => {}
+            Output::Stream => println!("{line}"),
+            Output::Buffer(lines) => lines.push(OutputLine::Stdout(line)),
+        }
+    }
+
+    pub fn eprintln(&mut self, line: String) {
+        match self {
+            Output::Silent | Output::Stream => eprintln!("{line}"),
+            Output::Buffer(lines) => lines.push(OutputLine::Stderr(line)),
+        }
+    }
+
+    /// Print buffered output.
+    pub fn flush(self) {
+        if let Output::Buffer(lines) = self {
+            for line in lines {
+                match line {
+                    OutputLine::Stdout(line) => println!("{line}"),
+                    OutputLine::Stderr(line) => eprintln!("{line}"),
+                }
+            }
+        }
+    }
+}
+
+/// Runs the days of a [`run_multi`] invocation. Shared by all days, and by all threads of a parallel run.
+struct DayRunner {
+    /// [`None`] if the aggregate binary did not build, in which case every day is built on its own.
+    aggregate_bin: Option<PathBuf>,
+    answers: Answers,
+    is_release: bool,
+    is_timed: bool,
+    reject_outliers: bool,
+    timeouts: Timeouts,
+}
+
+impl DayRunner {
+    fn run(&self, puzzle: Puzzle, output: &mut Output) -> (DayReport, Option<Timing>) {
+        let day = puzzle.day;
+
+        output.println(format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+        output.println("------".into());
+
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
+            output.println("Not scaffolded.".into());
+            return (DayReport::new(day, DayStatus::NotScaffolded, vec![]), None);
+        }
+
+        if InputSource::Puzzle
+            .path(puzzle)
+            .is_some_and(|path| !path.exists())
+        {
+            output.println("No input.".into());
+            return (DayReport::new(day, DayStatus::NoInput, vec![]), None);
+        }
+
+        let bin = match &self.aggregate_bin {
+            Some(bin) => bin.clone(),
+            None => match child_commands::build_day(puzzle, self.is_release) {
+                Ok(bin) => bin,
+                Err(e) => {
+                    let details = match e {
+                        Error::CompileFailed(stderr) => build_excerpt(&stderr),
+                        e => vec![format!("{e:?}")],
+                    };
+                    output.println("Build failed.".into());
+                    return (DayReport::new(day, DayStatus::BuildFailed, details), None);
+                }
+            },
+        };
+
+        let run = match child_commands::run_solution(
+            &bin,
+            puzzle,
+            self.is_timed,
+            self.reject_outliers,
+            self.timeouts,
+            output,
+        ) {
+            Ok(run) => run,
+            Err(e) => {
+                let details = vec![format!("could not run solution: {e:?}")];
+                return (DayReport::new(day, DayStatus::Failed, details), None);
+            }
+        };
+
+        let timing = (!run.results.is_empty() || run.timed_out.is_some()).then(|| {
+            let mut timing = child_commands::timing_from_results(&run.results, day);
+            timing.timeouts.extend(run.timed_out);
+            timing
+        });
+
+        let verdict = verify_day(&self.answers, day, &run.results);
+        (DayReport::from_run(day, &run, &verdict), timing)
+    }
+}
+
+/// Run a set of days, `jobs` at a time. The output of each day is printed in day order.
+/// Only untimed runs should use more than one job, concurrent days skew each other's timings.
+pub fn run_multi(
+    year: Option<Year>,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    reject_outliers: bool,
+    timeouts: Timeouts,
+    jobs: usize,
+) -> MultiRun {
+    let aggregate_bin = child_commands::build_solutions(is_release).ok();
+    if aggregate_bin.is_none() {
+        eprintln!("Failed to build solutions, building days one by one.");
+    }
 
-    let mut need_space = false;
+    let runner = DayRunner {
+        aggregate_bin,
+        answers: Answers::read_from_file(year).unwrap_or_else(|e| {
+            eprintln!("{e}\nAnswers are not compared against the answer ledger.");
+            Answers::default()
+        }),
+        is_release,
+        is_timed,
+        reject_outliers,
+        timeouts,
+    };
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let puzzles: Vec<Puzzle> = all_days_in(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .map(|day| Puzzle::new(year, day))
+        .collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let mut timings: Vec<Timing> = Vec::with_capacity(puzzles.len());
+    let mut reports: Vec<DayReport> = Vec::with_capacity(puzzles.len());
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mut collect = |(report, timing): (DayReport, Option<Timing>)| {
+        reports.push(report);
+        timings.extend(timing);
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    if jobs <= 1 {
+        for (index, puzzle) in puzzles.iter().enumerate() {
+            if index > 0 {
+                println!();
+            }
+            let outcome = runner.run(*puzzle, &mut Output::Stream);
+            collect(outcome);
+        }
+    } else {
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.min(puzzles.len()) {
+                let (sender, next, runner, puzzles) = (sender.clone(), &next, &runner, &puzzles);
+                scope.spawn(move || loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(puzzle) = puzzles.get(index) else {
+                        break;
+                    };
+                    let mut output = Output::Buffer(vec![]);
+                    let outcome = runner.run(*puzzle, &mut output);
+                    if sender.send((index, outcome, output)).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(sender);
+
+            // days finish in any order, hold them back until all earlier days were printed.
+            let mut pending = BTreeMap::new();
+            let mut next_to_print = 0;
+
+            for (index, outcome, output) in receiver {
+                pending.insert(index, (outcome, output));
+
+                while let Some((outcome, output)) = pending.remove(&next_to_print) {
+                    if next_to_print > 0 {
+                        println!();
+                    }
+                    output.flush();
+                    collect(outcome);
+                    next_to_print += 1;
+                }
             }
         });
+    }
+
+    println!();
+    report::print_summary(&reports);
+
+    let timings = Timings { data: timings };
 
     if is_timed {
-        let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
     }
+
+    MultiRun { timings, reports }
 }
 
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    BuildFailed,
+    /// A day failed to compile, with the output of the compiler.
+    CompileFailed(String),
     IO(io::Error),
 }
 
@@ -60,46 +282,123 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: Puzzle) -> String {
+    format!("./src/bin/{puzzle}.rs")
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// All solutions are linked into the aggregate `all` binary, which runs one day per invocation.
+/// This module encapsulates interaction with this binary, both invoking it as well as collecting its result records.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{get_path_for_bin, Error, Output, Timeouts};
+    use crate::template::protocol::{parse_started, PartResult, LAST_PART, PARSE_PART};
+    use crate::template::runner::{
+        format_duration, format_memory, format_parse, format_result, format_stats,
+    };
+    use crate::template::stats::Stats;
+    use crate::template::{Day, Puzzle, ANSI_BOLD, ANSI_RESET};
     use std::{
+        env,
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
+        sync::mpsc::{self, RecvTimeoutError},
         thread,
+        time::Instant,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Build the aggregate binary once and return the path to its executable.
+    pub fn build_solutions(is_release: bool) -> Result<PathBuf, Error> {
+        let mut args = vec!["build", "--quiet", "--bin", "all"];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        let status = Command::new("cargo").args(&args).status()?;
+
+        if !status.success() {
+            return Err(Error::BuildFailed);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        bin_path("all", is_release)
+    }
+
+    /// Build the binary of a single day, capturing the output of the compiler if it fails.
+    pub fn build_day(puzzle: Puzzle, is_release: bool) -> Result<PathBuf, Error> {
+        let bin_name = puzzle.to_string();
+        let mut args = vec!["build", "--quiet", "--bin", bin_name.as_str()];
 
         if is_release {
             args.push("--release");
         }
 
+        let output = Command::new("cargo").args(&args).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            return Err(Error::CompileFailed(stderr));
+        }
+
+        bin_path(&bin_name, is_release)
+    }
+
+    fn bin_path(bin_name: &str, is_release: bool) -> Result<PathBuf, Error> {
+        // the runner itself lives in `target/<profile>`, the solution binaries are built next to it.
+        let target_dir = env::current_exe()?
+            .parent()
+            .and_then(Path::parent)
+            .ok_or(Error::BuildFailed)?
+            .to_path_buf();
+
+        let profile = if is_release { "release" } else { "debug" };
+
+        Ok(target_dir
+            .join(profile)
+            .join(format!("{bin_name}{}", env::consts::EXE_SUFFIX)))
+    }
+
+    /// The result records of a solution, and how its process ended.
+    #[derive(Debug, Default)]
+    pub struct SolutionRun {
+        pub results: Vec<PartResult>,
+        /// The part that was killed because the solution ran into a timeout.
+        pub timed_out: Option<u8>,
+        /// [`None`] if the process was terminated by a signal, or did not run at all.
+        pub exit_code: Option<i32>,
+        pub stderr: Vec<String>,
+    }
+
+    /// Run the solution for a given day through the aggregate binary (or the day's own binary) and collect its results.
+    /// Results and any other output of the solution are written to `output` as they arrive.
+    pub fn run_solution(
+        bin: &Path,
+        puzzle: Puzzle,
+        is_timed: bool,
+        reject_outliers: bool,
+        timeouts: Timeouts,
+        output: &mut Output,
+    ) -> Result<SolutionRun, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
+            return Ok(SolutionRun::default());
+        }
+
+        let bin_name = puzzle.to_string();
+        let mut args = vec![bin_name.as_str(), "--format", "json"];
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
+
+            if reject_outliers {
+                args.push("--reject-outliers");
+            }
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr while reading result records from stdout.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(bin)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -108,85 +407,162 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut run = SolutionRun::default();
+
+        // stderr is kept to explain failures, and forwarded as it arrives unless it is buffered.
+        let forward_stderr = !matches!(output, Output::Buffer(_));
+        let stderr_thread = thread::spawn(move || {
+            stderr
+                .lines()
+                .map_while(Result::ok)
+                .inspect(|line| {
+                    if forward_stderr {
+                        eprintln!("{line}");
+                    }
+                })
+                .collect::<Vec<String>>()
+        });
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        // stdout is read on its own thread, so that waiting for the next line can time out.
+        let (sender, receiver) = mpsc::channel();
+        let stdout_thread = thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let day_deadline = timeouts.day.map(|timeout| Instant::now() + timeout);
+        let mut part_deadline = None;
+        let mut running: Option<u8> = None;
+
+        loop {
+            let line = match day_deadline.into_iter().chain(part_deadline).min() {
+                Some(deadline) => {
+                    receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            let line = match line {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Disconnected) => break,
+                Err(RecvTimeoutError::Timeout) => {
+                    let part = running.unwrap_or_else(|| next_part(&run.results));
+                    let label = if part == PARSE_PART {
+                        "Parse".into()
+                    } else {
+                        format!("Part {part}")
+                    };
+                    output.println(format!("{label}: {ANSI_BOLD}TIMEOUT{ANSI_RESET}"));
+                    run.timed_out = Some(part);
+                    cmd.kill()?;
+                    break;
+                }
+            };
+
+            if let Some(part) = parse_started(&line) {
+                running = Some(part);
+                part_deadline = timeouts.part.map(|timeout| Instant::now() + timeout);
+                continue;
+            }
+
+            match line.parse::<PartResult>() {
+                Ok(result) => {
+                    print_part_result(&result, output);
+                    running = None;
+                    part_deadline = None;
+                    run.results.push(result);
+                }
+                // forward any output of the solution itself, e.g. debug prints.
+                Err(_) => output.println(line),
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        run.exit_code = cmd.wait()?.code();
+        stdout_thread.join().map_err(|_| Error::BrokenPipe)?;
+        run.stderr = stderr_thread.join().map_err(|_| Error::BrokenPipe)?;
 
-        Ok(output)
+        if !forward_stderr {
+            for line in &run.stderr {
+                output.eprintln(line.clone());
+            }
+        }
+
+        Ok(run)
+    }
+
+    /// The part a day that times out between two parts is attributed to, i.e. the part that would run next.
+    /// A day that times out after its last part is attributed to that part.
+    fn next_part(results: &[PartResult]) -> u8 {
+        let last = results.last().map_or(PARSE_PART, |r| r.part);
+        (last + 1).min(LAST_PART)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
+    fn print_part_result(result: &PartResult, output: &mut Output) {
+        let duration_str = format_duration(&result.duration, result.samples);
+        if result.part == PARSE_PART && result.error.is_none() {
+            output.println(format_parse(&duration_str));
+        } else if result.part == PARSE_PART {
+            output.println(format_result(&result.to_answer(), "Parse", &duration_str));
+        } else {
+            output.println(format_result(
+                &result.to_answer(),
+                &format!("Part {}", result.part),
+                &duration_str,
+            ));
+        }
+        if let Some(stats) = &result.stats {
+            output.println(format_stats(stats));
+            if let Some(memory) = &result.memory {
+                output.println(format_memory(memory));
+            }
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn timing_from_results(results: &[PartResult], day: Day) -> super::Timing {
+        let mut timing = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            errors: vec![],
+            timeouts: vec![],
+            memory: vec![],
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        for result in results {
+            if let Some(error) = &result.error {
+                timing.errors.push((result.part, error.clone()));
+            }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            // a parse step has no answer, it only fails with an error.
+            if result.error.is_some() || (!result.is_success() && result.part != PARSE_PART) {
+                continue;
+            }
 
-                timings.total_nanos += nanos;
-            });
+            if let Some(memory) = result.memory {
+                timing.memory.push((result.part, memory));
+            }
 
-        timings
-    }
+            let stats = result
+                .stats
+                .unwrap_or_else(|| Stats::single(result.duration));
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+            match result.part {
+                PARSE_PART => timing.parse = Some(stats),
+                1 => timing.part_1 = Some(stats),
+                2 => timing.part_2 = Some(stats),
+                _ => continue,
+            }
+
+            timing.total_nanos += stats.median.as_nanos() as f64;
+        }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        timing
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,53 +581,121 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use std::time::Duration;
+
+        use super::{next_part, timing_from_results};
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{
+                protocol::{PartResult, PARSE_PART},
+                stats::Stats,
+            },
+        };
+
+        fn result(part: u8, answer: Option<&str>, nanos: u64, samples: u128) -> PartResult {
+            PartResult {
+                part,
+                answer: answer.map(Into::into),
+                error: None,
+                duration: Duration::from_nanos(nanos),
+                samples,
+                stats: None,
+                memory: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = timing_from_results(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    result(1, Some("0"), 74, 100_000),
+                    result(2, Some("10"), 74_130_000, 99_999),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap().median, Duration::from_nanos(74));
+            assert_eq!(res.part_2.unwrap().median, Duration::from_nanos(74_130_000));
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_with_patterns_in_answers() {
+            let res = timing_from_results(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    result(1, Some("@ @ @ ( ) ms"), 2_000_000_000, 5),
+                    result(2, Some("10s"), 100_000_000, 1),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap().median, Duration::from_secs(2));
+            assert_eq!(res.part_2.unwrap().median, Duration::from_millis(100));
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn collects_parse_times() {
+            let parse = result(PARSE_PART, None, 30, 1);
+            let res = timing_from_results(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    parse,
+                    result(1, Some("1"), 10, 1),
+                    result(2, Some("2"), 20, 1),
                 ],
                 day!(1),
             );
+            assert_approx_eq!(res.total_nanos, 60_f64);
+            assert_eq!(res.parse.unwrap().median, Duration::from_nanos(30));
+        }
+
+        #[test]
+        fn records_errors() {
+            let mut failed = result(2, None, 10, 1);
+            failed.error = Some("unexpected end of input".into());
+            let res = timing_from_results(&[result(1, Some("1"), 10, 1), failed], day!(1));
+            assert_approx_eq!(res.total_nanos, 10_f64);
+            assert_eq!(res.part_2, None);
+            assert_eq!(res.errors, vec![(2, "unexpected end of input".into())]);
+        }
+
+        #[test]
+        fn records_parse_errors() {
+            let mut failed = result(PARSE_PART, None, 10, 1);
+            failed.error = Some("the input could not be parsed".into());
+            let res = timing_from_results(&[failed], day!(1));
+            assert_eq!(res.parse, None);
+            assert_eq!(
+                res.errors,
+                vec![(PARSE_PART, "the input could not be parsed".into())]
+            );
+        }
+
+        #[test]
+        fn prefers_benchmark_statistics() {
+            let mut benched = result(1, Some("1"), 30, 3);
+            benched.stats = Stats::from_samples(&[10, 30, 500].map(Duration::from_nanos), false);
+            let res = timing_from_results(&[benched], day!(1));
+            assert_approx_eq!(res.total_nanos, 30_f64);
+            assert_eq!(res.part_1.unwrap().max, Duration::from_nanos(500));
+            assert_eq!(res.part_1.unwrap().samples, 3);
+        }
+
+        #[test]
+        fn collects_missing_parts() {
+            let res =
+                timing_from_results(&[result(1, None, 10, 1), result(2, None, 10, 1)], day!(1));
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn attributes_timeouts_between_parts() {
+            assert_eq!(next_part(&[]), 1);
+            assert_eq!(next_part(&[result(PARSE_PART, None, 10, 1)]), 1);
+            assert_eq!(next_part(&[result(1, Some("1"), 10, 1)]), 2);
+            let done = [result(1, Some("1"), 10, 1), result(2, Some("2"), 10, 1)];
+            assert_eq!(next_part(&done), 2);
+        }
     }
 }
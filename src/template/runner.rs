@@ -2,26 +2,328 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::atomic::{AtomicI32, Ordering};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers::Answers;
+use crate::template::input::InputSource;
+use crate::template::memory::{self, format_bytes, MemoryStats};
+use crate::template::protocol::{is_json_format, started_line, PartResult, PARSE_PART};
+use crate::template::stats::Stats;
+use crate::template::variants::{
+    describe_disagreement, disagreement, print_comparison, VariantResult,
+};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client, Puzzle, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+// NOTE: declared here rather than in `solution!` so that the aggregate `all` binary has a single allocator.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Exit code of a submission that was not sent because the answer ledger knows it to be wrong.
+const REJECTED_LOCALLY_EXIT_CODE: i32 = 7;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// Exit code of the solution process, set by an answer submission.
+static EXIT_CODE: AtomicI32 = AtomicI32::new(0);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// The exit code the solution process should terminate with once all parts ran.
+pub fn exit_code() -> i32 {
+    EXIT_CODE.load(Ordering::Relaxed)
+}
+
+/// The outcome of a solution part, as printed and recorded by the runner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Solved(String),
+    /// The part returned [`None`].
+    Unsolved,
+    /// The part returned an error, e.g. because the input could not be parsed.
+    Failed(String),
+}
+
+impl Answer {
+    pub fn from_parts(answer: Option<String>, error: Option<String>) -> Self {
+        match (answer, error) {
+            (Some(answer), _) => Answer::Solved(answer),
+            (None, Some(error)) => Answer::Failed(error),
+            (None, None) => Answer::Unsolved,
+        }
+    }
+
+    pub fn answer(&self) -> Option<&str> {
+        match self {
+            Answer::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Answer::Failed(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// The return type of a solution part: either [`Option<T>`] or [`Result<T, E>`].
+pub trait PartOutput {
+    fn to_answer(&self) -> Answer;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn to_answer(&self) -> Answer {
+        match self {
+            Some(answer) => Answer::Solved(answer.to_string()),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    fn to_answer(&self) -> Answer {
+        match self {
+            Ok(answer) => Answer::Solved(answer.to_string()),
+            Err(e) => Answer::Failed(e.to_string()),
+        }
+    }
+}
+
+/// The return type of a parse function that can fail: either [`Option<T>`] or [`Result<T, E>`].
+pub trait ParseOutput {
+    type Parsed;
+
+    /// The parsed input, or the error to report on the parse row.
+    fn into_parsed(self) -> Result<Self::Parsed, String>;
+}
+
+impl<T> ParseOutput for Option<T> {
+    type Parsed = T;
+
+    fn into_parsed(self) -> Result<T, String> {
+        self.ok_or_else(|| "the input could not be parsed".into())
+    }
+}
+
+impl<T, E: Display> ParseOutput for Result<T, E> {
+    type Parsed = T;
+
+    fn into_parsed(self) -> Result<T, String> {
+        self.map_err(|e| e.to_string())
+    }
+}
+
+/// Run the parse step of a solution that parses its input once for both parts, and time it as its own row.
+/// Returns [`None`] if parsing failed, the error is reported on the parse row and the parts should be skipped.
+pub fn run_parse<'a, O: ParseOutput>(
+    func: impl Fn(&'a str) -> O,
+    input: &'a str,
+) -> Option<O::Parsed> {
+    let is_json = is_json_format();
+
+    if is_json {
+        println!("{}", started_line(PARSE_PART));
+    }
+
+    let (parsed, stats, memory) = run_timed(func, input, |_| {});
+    let parsed = parsed.into_parsed();
+
+    let duration = stats.median;
+    let samples = stats.samples + stats.outliers;
+    let stats = (samples > 1).then_some(stats);
+
+    if is_json {
+        let record = PartResult {
+            part: PARSE_PART,
+            answer: None,
+            error: parsed.as_ref().err().cloned(),
+            duration,
+            samples,
+            stats,
+            memory,
+        };
+        println!("{}", record.to_json_line());
+    } else {
+        let duration_str = format_duration(&duration, samples);
+        match &parsed {
+            Ok(_) => print_parse(&duration_str),
+            Err(e) => print_result(&Answer::Failed(e.clone()), "Parse", &duration_str),
+        }
+        if let Some(stats) = &stats {
+            print_stats(stats);
+            if let Some(memory) = &memory {
+                print_memory(memory);
+            }
+        }
+    }
+
+    parsed.ok()
+}
+
+pub fn run_part<I: Clone, O: PartOutput>(
+    func: impl Fn(I) -> O,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+) -> Option<String> {
+    let (result, _) = measure_part(func, input, part, &format!("Part {part}"));
+    let answer = result.answer().map(ToString::to_string);
+
+    if let Some(answer) = &answer {
+        if let Some(code) = submit_result(answer, puzzle, part) {
+            EXIT_CODE.store(code, Ordering::Relaxed);
+        }
+    }
+
+    answer
+}
+
+/// Runs the variants of a part that were registered with `solution!`.
+/// The first variant is the primary one: its answer is returned and submitted.
+/// In JSON mode, only the primary variant is timed and reported. The other variants are run once to cross-check it,
+/// and a disagreement is reported as the error of the part.
+pub struct PartVariants {
+    puzzle: Puzzle,
+    part: u8,
+    results: Vec<VariantResult>,
+    record: Option<PartResult>,
+}
+
+impl PartVariants {
+    pub fn new(puzzle: Puzzle, part: u8) -> Self {
+        Self {
+            puzzle,
+            part,
+            results: vec![],
+            record: None,
+        }
+    }
+
+    pub fn run<I: Clone, O: PartOutput>(&mut self, name: &str, func: impl Fn(I) -> O, input: I) {
+        if is_json_format() && !self.results.is_empty() {
+            let timer = Instant::now();
+            let answer = func(input).to_answer();
+
+            self.results.push(VariantResult {
+                name: name.into(),
+                answer,
+                duration: timer.elapsed(),
+            });
+            return;
+        }
+
+        let label = format!("Part {} ({name})", self.part);
+        let (answer, duration, record) = time_part(func, input, self.part, &label);
+
+        self.record = record;
+        self.results.push(VariantResult {
+            name: name.into(),
+            answer,
+            duration,
+        });
+    }
+
+    pub fn finish(mut self) -> Option<String> {
+        let answer = self
+            .results
+            .first()?
+            .answer
+            .answer()
+            .map(ToString::to_string);
+
+        if let Some(mut record) = self.record.take() {
+            if let Some(error) = describe_disagreement(&self.results) {
+                record.answer = None;
+                record.error = Some(error);
+            }
+            println!("{}", record.to_json_line());
+            return answer;
+        }
+
+        print_comparison(self.part, &self.results);
+
+        if disagreement(&self.results).is_some() {
+            EXIT_CODE.store(1, Ordering::Relaxed);
+            return answer;
+        }
+
+        if let Some(answer) = &answer {
+            if let Some(code) = submit_result(answer, self.puzzle, self.part) {
+                EXIT_CODE.store(code, Ordering::Relaxed);
+            }
+        }
+
+        answer
     }
 }
 
+/// Time a part and print its result, or emit it as a JSON record.
+/// Returns its answer and its (median) duration.
+fn measure_part<I: Clone, O: PartOutput>(
+    func: impl Fn(I) -> O,
+    input: I,
+    part: u8,
+    label: &str,
+) -> (Answer, Duration) {
+    let (answer, duration, record) = time_part(func, input, part, label);
+
+    if let Some(record) = record {
+        println!("{}", record.to_json_line());
+    }
+
+    (answer, duration)
+}
+
+/// Time a part and print its result. In JSON mode, its record is returned instead so that the caller can emit it.
+fn time_part<I: Clone, O: PartOutput>(
+    func: impl Fn(I) -> O,
+    input: I,
+    part: u8,
+    label: &str,
+) -> (Answer, Duration, Option<PartResult>) {
+    let is_json = is_json_format();
+
+    if is_json {
+        println!("{}", started_line(part));
+    }
+
+    let (result, stats, memory) = run_timed(func, input, |result| {
+        if !is_json {
+            print_result(&result.to_answer(), label, "");
+        }
+    });
+    let result = result.to_answer();
+
+    // a single execution reports its duration, a benched part reports its median.
+    let duration = stats.median;
+    let samples = stats.samples + stats.outliers;
+    let stats = (samples > 1).then_some(stats);
+
+    if is_json {
+        let record = PartResult {
+            part,
+            answer: result.answer().map(ToString::to_string),
+            error: result.error().map(ToString::to_string),
+            duration,
+            samples,
+            stats,
+            memory,
+        };
+        return (result, duration, Some(record));
+    }
+
+    print_result(&result, label, &format_duration(&duration, samples));
+    if let Some(stats) = &stats {
+        print_stats(stats);
+        if let Some(memory) = &memory {
+            print_memory(memory);
+        }
+    }
+
+    (result, duration, None)
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
@@ -29,38 +331,55 @@ fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Stats, Option<MemoryStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        // only the first execution is measured, the benchmark iterations would just repeat it.
+        memory::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let args: Vec<String> = env::args().collect();
+
+    let stats = if args.iter().any(|x| x == "--time") {
+        let reject_outliers = args.iter().any(|x| x == "--reject-outliers");
+        bench(func, input, &base_time, reject_outliers)
     } else {
-        (base_time, 1)
+        Stats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats, memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Bench a solution part: after a few warmup iterations, every iteration is sampled individually.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    reject_outliers: bool,
+) -> Stats {
+    if !is_json_format() {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // warm up caches and branch predictors before sampling.
+    let warmup_iterations = cmp::max(bench_iterations / 10, 1);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -71,22 +390,10 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_samples(&timers, reject_outliers).unwrap_or_else(|| Stats::single(*base_time))
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
-
-fn format_duration(duration: &Duration, samples: u128) -> String {
+pub(crate) fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
@@ -94,49 +401,87 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+pub(crate) fn print_stats(stats: &Stats) {
+    println!("{}", format_stats(stats));
+}
+
+pub(crate) fn format_stats(stats: &Stats) -> String {
+    let mut line = format!(
+        "  {ANSI_ITALIC}min {:.1?}, max {:.1?}, σ {:.1?}, p95 {:.1?}, p99 {:.1?}",
+        stats.min, stats.max, stats.std_dev, stats.p95, stats.p99
+    );
+    if stats.outliers > 0 {
+        line.push_str(&format!(", {} outliers rejected", stats.outliers));
+    }
+    format!("{line}{ANSI_RESET}")
+}
+
+pub(crate) fn print_memory(memory: &MemoryStats) {
+    println!("{}", format_memory(memory));
+}
+
+pub(crate) fn format_memory(memory: &MemoryStats) -> String {
+    format!(
+        "  {ANSI_ITALIC}peak heap {}, {} allocations ({} total){ANSI_RESET}",
+        format_bytes(memory.peak_bytes),
+        memory.allocations,
+        format_bytes(memory.bytes_allocated)
+    )
+}
+
+pub(crate) fn print_parse(duration_str: &str) {
+    println!("{}", format_parse(duration_str));
+}
+
+pub(crate) fn format_parse(duration_str: &str) -> String {
+    format!("Parse:{duration_str}")
+}
+
+pub(crate) fn print_result(result: &Answer, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
+    if !is_intermediate_result {
+        print!("\r");
+        println!("{}", format_result(result, part, duration_str));
+        return;
+    }
+
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
+        Answer::Solved(result) if result.contains('\n') => print!("{part}: ▼ "),
+        Answer::Solved(result) => print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}"),
+        Answer::Unsolved => print!("{part}: ✖"),
+        Answer::Failed(error) if error.contains('\n') => print!("{part}: ✖ ▼ "),
+        Answer::Failed(error) => print!("{part}: ✖ {ANSI_ITALIC}{error}{ANSI_RESET}"),
+    }
+}
+
+/// The final output of a part, which may span several lines.
+pub(crate) fn format_result(result: &Answer, part: &str, duration_str: &str) -> String {
+    match result {
+        Answer::Solved(result) if result.contains('\n') => {
+            format!("{part}: ▼ {duration_str}\n{result}")
         }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
+        Answer::Solved(result) => format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}"),
+        // padded to overwrite the intermediate result.
+        Answer::Unsolved => format!("{part}: ✖             "),
+        Answer::Failed(error) if error.contains('\n') => format!(
+            "{part}: ✖ ▼ {duration_str}\n{ANSI_ITALIC}{}{ANSI_RESET}",
+            error.trim_end()
+        ),
+        Answer::Failed(error) => {
+            format!("{part}: ✖ {ANSI_ITALIC}{error}{ANSI_RESET}{duration_str}")
         }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a session token is configured.
+///  3. the solution ran against the puzzle input.
+///  4. the answer ledger does not know the answer to be wrong.
+///
+/// Returns the exit code of the submission.
+fn submit_result(answer: &str, puzzle: Puzzle, part: u8) -> Option<i32> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +504,40 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if !InputSource::from_args().is_ok_and(|source| source.is_puzzle_input()) {
+        eprintln!("Not submitting: the solution did not run against the puzzle input.");
+        return Some(1);
+    }
+
+    let mut answers = match Answers::read_from_file(puzzle.year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Not submitting: {e}");
+            eprintln!("Fix or remove the answer ledger first.");
+            return Some(1);
+        }
+    };
+
+    if let Err(rejection) = answers.check(puzzle.day, part, answer) {
+        eprintln!("Not submitting {answer}: {rejection}");
+        return Some(REJECTED_LOCALLY_EXIT_CODE);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+
+    match aoc_client::submit(puzzle, part, answer) {
+        Ok(outcome) => {
+            println!("{outcome}");
+            let code = outcome.exit_code();
+            answers.record(puzzle.day, part, answer, outcome);
+            if let Err(e) = answers.store_file(puzzle.year) {
+                eprintln!("failed to store answer in ledger: {e}");
+            }
+            Some(code)
+        }
+        Err(e) => {
+            eprintln!("failed to submit result: {e}");
+            Some(1)
+        }
+    }
 }
@@ -0,0 +1,214 @@
+/// Summary statistics over benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Statistics of a benchmarked part. All durations are computed over the samples that were kept.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    /// Number of samples the statistics were computed from.
+    pub samples: u128,
+    /// Number of samples that were discarded as outliers.
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Compute statistics over `samples`.
+    /// If `reject_outliers` is set, samples outside of 1.5 times the interquartile range are discarded first.
+    /// Returns [`None`] if there are no samples.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[Duration], reject_outliers: bool) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let total = sorted.len();
+
+        if reject_outliers && total >= 4 {
+            let q1 = percentile(&sorted, 25.0);
+            let q3 = percentile(&sorted, 75.0);
+            let fence = 1.5 * (q3 - q1);
+            sorted.retain(|x| *x >= q1 - fence && *x <= q3 + fence);
+        }
+
+        let count = sorted.len();
+        let mean = sorted.iter().sum::<f64>() / count as f64;
+
+        let variance = if count > 1 {
+            sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (count - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            mean: from_nanos(mean),
+            median: from_nanos(percentile(&sorted, 50.0)),
+            min: from_nanos(*sorted.first()?),
+            max: from_nanos(*sorted.last()?),
+            std_dev: from_nanos(variance.sqrt()),
+            p95: from_nanos(percentile(&sorted, 95.0)),
+            p99: from_nanos(percentile(&sorted, 99.0)),
+            samples: count as u128,
+            outliers: (total - count) as u128,
+        })
+    }
+
+    /// Statistics for a part that was executed exactly once.
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            mean: duration,
+            median: duration,
+            min: duration,
+            max: duration,
+            std_dev: Duration::ZERO,
+            p95: duration,
+            p99: duration,
+            samples: 1,
+            outliers: 0,
+        }
+    }
+}
+
+/// Linearly interpolated percentile of already sorted values.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0],
+        len => {
+            let rank = p / 100.0 * (len - 1) as f64;
+            let lower = rank.floor() as usize;
+            let upper = rank.ceil() as usize;
+            sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+        }
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let durations = [
+            ("mean_nanos", value.mean),
+            ("median_nanos", value.median),
+            ("min_nanos", value.min),
+            ("max_nanos", value.max),
+            ("std_dev_nanos", value.std_dev),
+            ("p95_nanos", value.p95),
+            ("p99_nanos", value.p99),
+        ];
+
+        for (key, duration) in durations {
+            map.insert(key.into(), JsonValue::Number(duration.as_nanos() as f64));
+        }
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        let duration = |key: &str| number(key).map(|v| Duration::from_nanos(v as u64));
+
+        Ok(Stats {
+            mean: duration("mean_nanos")?,
+            median: duration("median_nanos")?,
+            min: duration("min_nanos")?,
+            max: duration("max_nanos")?,
+            std_dev: duration("std_dev_nanos")?,
+            p95: duration("p95_nanos")?,
+            p99: duration("p99_nanos")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::Stats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[], false), None);
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = Stats::from_samples(&nanos(&[50, 10, 40, 20, 30]), false).unwrap();
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.std_dev, Duration::from_nanos(16));
+        assert_eq!(stats.p95, Duration::from_nanos(48));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn keeps_outliers_by_default() {
+        let stats = Stats::from_samples(&nanos(&[10, 11, 12, 13, 1000]), false).unwrap();
+        assert_eq!(stats.max, Duration::from_nanos(1000));
+        assert_eq!(stats.median, Duration::from_nanos(12));
+        assert_eq!(stats.samples, 5);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[10, 11, 12, 13, 1000]), true).unwrap();
+        assert_eq!(stats.max, Duration::from_nanos(13));
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.outliers, 1);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let stats = Stats::from_samples(&nanos(&[5, 7, 100, 9]), false).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(Stats::try_from(&json).unwrap(), stats);
+    }
+}
@@ -1,17 +1,46 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::memory::MemoryStats;
+use crate::template::stats::Stats;
+use crate::template::{data_dir, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Time spent parsing the input, for solutions that parse once for both parts.
+    pub parse: Option<Stats>,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
     pub total_nanos: f64,
+    /// Error messages of the parts that returned an error, by part.
+    pub errors: Vec<(u8, String)>,
+    /// Parts that were killed because they ran into a timeout.
+    pub timeouts: Vec<u8>,
+    /// Heap usage, by part. Empty if allocations were not counted.
+    pub memory: Vec<(u8, MemoryStats)>,
+}
+
+impl Timing {
+    /// Whether a part returned an error instead of an answer.
+    pub fn is_failed(&self, part: u8) -> bool {
+        self.errors.iter().any(|(p, _)| *p == part)
+    }
+
+    /// Whether a part was killed because it ran into a timeout.
+    pub fn is_timed_out(&self, part: u8) -> bool {
+        self.timeouts.contains(&part)
+    }
+
+    /// Heap usage of a part, if allocations were counted.
+    pub fn memory(&self, part: u8) -> Option<&MemoryStats> {
+        self.memory
+            .iter()
+            .find_map(|(p, memory)| (*p == part).then_some(memory))
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,16 +51,18 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate the timings of a year to a JSON file.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let dir = data_dir(year);
+        fs::create_dir_all(&dir)?;
+        let mut file = fs::File::create(dir.join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate the timings of a year from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        fs::read_to_string(data_dir(year).join(TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,7 +82,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -114,8 +145,12 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::from(parse));
+        }
+
+        let part_1 = value.part_1.as_ref().map(JsonValue::from);
+        let part_2 = value.part_2.as_ref().map(JsonValue::from);
 
         map.insert(
             "part_1".into(),
@@ -133,6 +168,45 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if !value.errors.is_empty() {
+            map.insert(
+                "errors".into(),
+                JsonValue::Object(
+                    value
+                        .errors
+                        .iter()
+                        .map(|(part, error)| (part.to_string(), JsonValue::String(error.clone())))
+                        .collect(),
+                ),
+            );
+        }
+
+        if !value.timeouts.is_empty() {
+            map.insert(
+                "timeouts".into(),
+                JsonValue::Array(
+                    value
+                        .timeouts
+                        .iter()
+                        .map(|part| JsonValue::Number(f64::from(*part)))
+                        .collect(),
+                ),
+            );
+        }
+
+        if !value.memory.is_empty() {
+            map.insert(
+                "memory".into(),
+                JsonValue::Object(
+                    value
+                        .memory
+                        .iter()
+                        .map(|(part, memory)| (part.to_string(), JsonValue::from(memory)))
+                        .collect(),
+                ),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -140,6 +214,7 @@ impl From<&Timing> for JsonValue {
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
         let json = value
             .get::<HashMap<String, JsonValue>>()
@@ -148,81 +223,211 @@ impl TryFrom<&JsonValue> for Timing {
         let day = json
             .get("day")
             .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
+            // timings are stored per year, days are checked against the event of their year when they are run.
+            .and_then(|day| day.parse().ok().and_then(Day::new))
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        // solutions without a parse step have no `parse` key.
+        let parse = match json.get("parse") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Stats::try_from(v)?),
+        };
+
+        let part = |key: &str| match json.get(key) {
+            None => Err(format!("Expected timing.{key} to be null or stats.")),
+            Some(v) if v.is_null() => Ok(None),
+            // timings stored before statistics were recorded hold a formatted duration, e.g. `74.13ns`.
+            Some(JsonValue::String(duration)) => parse_legacy_duration(duration)
+                .map(|duration| Some(Stats::single(duration)))
+                .ok_or(format!("Expected timing.{key} to be a duration.")),
+            Some(v) => Stats::try_from(v).map(Some),
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let part_1 = part("part_1")?;
+        let part_2 = part("part_2")?;
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // timings stored before errors were recorded have no `errors` key.
+        let mut errors: Vec<(u8, String)> = match json.get("errors") {
+            None => vec![],
+            Some(v) => v
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected timing.errors to be an object.")?
+                .iter()
+                .map(|(part, error)| Some((part.parse().ok()?, error.get::<String>()?.clone())))
+                .collect::<Option<_>>()
+                .ok_or("Expected timing.errors to map parts to strings.")?,
+        };
+        errors.sort_unstable();
+
+        // timings stored before timeouts were recorded have no `timeouts` key.
+        let timeouts: Vec<u8> = match json.get("timeouts") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.timeouts to be an array.")?
+                .iter()
+                .map(|part| part.get::<f64>().map(|p| *p as u8))
+                .collect::<Option<_>>()
+                .ok_or("Expected timing.timeouts to be an array of parts.")?,
+        };
+
+        // timings stored before allocations were counted have no `memory` key.
+        let mut memory: Vec<(u8, MemoryStats)> = match json.get("memory") {
+            None => vec![],
+            Some(v) => v
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected timing.memory to be an object.")?
+                .iter()
+                .map(|(part, memory)| {
+                    let part = part
+                        .parse()
+                        .map_err(|_| "Expected timing.memory to be keyed by part.")?;
+                    Ok((part, MemoryStats::try_from(memory)?))
+                })
+                .collect::<Result<_, String>>()?,
+        };
+        memory.sort_unstable_by_key(|(part, _)| *part);
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1,
+            part_2,
             total_nanos,
+            errors,
+            timeouts,
+            memory,
         })
     }
 }
 
+/// Parse a duration as formatted by [`Duration`]'s debug output, e.g. `74.13ns`, `1.5µs` or `2s`.
+fn parse_legacy_duration(s: &str) -> Option<Duration> {
+    let units = [
+        ("ns", 1e-9),
+        ("µs", 1e-6),
+        ("us", 1e-6),
+        ("ms", 1e-3),
+        ("s", 1.0),
+    ];
+    let (value, scale) = units
+        .iter()
+        .find_map(|(unit, scale)| Some((s.strip_suffix(unit)?, scale)))?;
+    let value: f64 = value.trim().parse().ok()?;
+    Duration::try_from_secs_f64(value * scale).ok()
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use std::time::Duration;
+
+    use crate::{day, template::stats::Stats};
 
     use super::{Timing, Timings};
 
+    fn stats(millis: u64) -> Stats {
+        Stats::single(Duration::from_millis(millis))
+    }
+
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(stats(10)),
+                    part_2: Some(stats(20)),
                     total_nanos: 3e+10,
+                    errors: vec![],
+                    timeouts: vec![],
+                    memory: vec![],
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(stats(30)),
+                    part_2: Some(stats(40)),
                     total_nanos: 7e+10,
+                    errors: vec![],
+                    timeouts: vec![],
+                    memory: vec![],
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(stats(40)),
                     part_2: None,
                     total_nanos: 4e+10,
+                    errors: vec![],
+                    timeouts: vec![],
+                    memory: vec![],
                 },
             ],
         }
     }
 
     mod deserialization {
+        use std::time::Duration;
+
         use crate::{day, template::timings::Timings};
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "mean_nanos": 1000000, "median_nanos": 900000, "min_nanos": 800000, "max_nanos": 2000000, "std_dev_nanos": 1000, "p95_nanos": 1500000, "p99_nanos": 1900000, "samples": 100, "outliers": 0 }, "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            let part_1 = timing.part_1.unwrap();
+            assert_eq!(part_1.median, Duration::from_micros(900));
+            assert_eq!(part_1.samples, 100);
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn migrates_legacy_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.13ns", "part_2": "1.5ms", "total_nanos": 1500074.13 }, { "day": "02", "part_1": "2s", "part_2": null, "total_nanos": 2000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.data[0].part_1.unwrap();
+            assert_eq!(part_1.median, Duration::from_nanos(74));
+            assert_eq!(part_1.samples, 1);
+            assert_eq!(
+                timings.data[0].part_2.unwrap().median,
+                Duration::from_micros(1500)
+            );
+            assert_eq!(
+                timings.data[1].part_1.unwrap().median,
+                Duration::from_secs(2)
+            );
+            assert_eq!(timings.data[1].part_2, None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "mean_nanos": 1 }, "part_2": "fast", "total_nanos": 0 }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
+        #[test]
+        fn handles_errors() {
+            let json = r#"{ "data": [{ "day": "02", "part_1": null, "part_2": null, "total_nanos": 0, "errors": { "2": "b", "1": "a" } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(
+                timings.data[0].errors,
+                vec![(1, "a".to_string()), (2, "b".to_string())]
+            );
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -247,6 +452,7 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::memory::MemoryStats;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,6 +472,53 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn round_trips_errors() {
+            let mut timings = get_mock_timings();
+            timings.data[2].errors = vec![(2, "unexpected token".into())];
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = crate::template::timings::Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[2].errors, vec![(2, "unexpected token".into())]);
+            assert_eq!(parsed.data[0].errors, vec![]);
+        }
+
+        #[test]
+        fn round_trips_parse_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[0].parse = Some(super::stats(5));
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = crate::template::timings::Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].parse, Some(super::stats(5)));
+            assert_eq!(parsed.data[1].parse, None);
+        }
+
+        #[test]
+        fn round_trips_timeouts() {
+            let mut timings = get_mock_timings();
+            timings.data[2].timeouts = vec![2];
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = crate::template::timings::Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[2].timeouts, vec![2]);
+            assert_eq!(parsed.data[2].is_timed_out(2), true);
+            assert_eq!(parsed.data[0].timeouts, vec![]);
+        }
+
+        #[test]
+        fn round_trips_memory() {
+            let memory = MemoryStats {
+                allocations: 3,
+                bytes_allocated: 2048,
+                peak_bytes: 1024,
+            };
+            let mut timings = get_mock_timings();
+            timings.data[1].memory = vec![(1, memory), (2, MemoryStats::default())];
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = crate::template::timings::Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[1].memory(1), Some(&memory));
+            assert_eq!(parsed.data[1].memory.len(), 2);
+            assert_eq!(parsed.data[0].memory(1), None);
+        }
     }
 
     mod is_day_complete {
@@ -274,18 +527,24 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
+        use super::stats;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: Some(stats(1)),
+                    part_2: Some(stats(2)),
                     total_nanos: 3_000_000_000_f64,
+                    errors: vec![],
+                    timeouts: vec![],
+                    memory: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +552,17 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: Some(stats(1)),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    errors: vec![],
+                    timeouts: vec![],
+                    memory: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +570,17 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    errors: vec![],
+                    timeouts: vec![],
+                    memory: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -323,7 +590,7 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
-        use super::get_mock_timings;
+        use super::{get_mock_timings, stats};
 
         #[test]
         fn handles_disjunct_timings() {
@@ -331,9 +598,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    errors: vec![],
+                    timeouts: vec![],
+                    memory: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +622,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    errors: vec![],
+                    timeouts: vec![],
+                    memory: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -365,6 +640,34 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_errors_of_both_sides() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_2 = None;
+            timings.data[0].errors = vec![(2, "stored".into())];
+
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    parse: None,
+                    part_1: None,
+                    part_2: Some(stats(40)),
+                    total_nanos: 4e+7,
+                    errors: vec![(1, "new".into())],
+                    timeouts: vec![],
+                    memory: vec![],
+                }],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data[0].day, day!(1));
+            assert_eq!(merged.data[0].errors, vec![(2, "stored".to_string())]);
+            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[1].part_1, None);
+            assert_eq!(merged.data[1].errors, vec![(1, "new".to_string())]);
+            assert_eq!(merged.data[2].errors, vec![]);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
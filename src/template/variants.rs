@@ -0,0 +1,166 @@
+/// Module that cross-checks the variants of a solution part and compares their timings.
+use std::time::Duration;
+
+use crate::template::runner::Answer;
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// The outcome of running a single variant of a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariantResult {
+    pub name: String,
+    pub answer: Answer,
+    pub duration: Duration,
+}
+
+/// The first variant whose answer differs from the answer of the primary (first) variant.
+pub fn disagreement(results: &[VariantResult]) -> Option<&VariantResult> {
+    let (primary, rest) = results.split_first()?;
+    rest.iter()
+        .find(|r| r.answer.answer() != primary.answer.answer())
+}
+
+/// Describe the first disagreeing variant, e.g. ``variant `fast` answered 41 instead of 42``.
+pub fn describe_disagreement(results: &[VariantResult]) -> Option<String> {
+    let primary = results.first()?;
+    let variant = disagreement(results)?;
+    Some(format!(
+        "variant `{}` answered {} instead of {}",
+        variant.name,
+        format_answer(&variant.answer),
+        format_answer(&primary.answer)
+    ))
+}
+
+/// How much faster or slower a variant ran than the primary variant, e.g. `12.50x faster`.
+#[allow(clippy::cast_precision_loss)]
+pub fn relative_speed(primary: Duration, variant: Duration) -> String {
+    let primary = primary.as_nanos().max(1) as f64;
+    let variant = variant.as_nanos().max(1) as f64;
+
+    if variant <= primary {
+        format!("{:.2}x faster", primary / variant)
+    } else {
+        format!("{:.2}x slower", variant / primary)
+    }
+}
+
+fn format_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Solved(answer) if answer.contains('\n') => "▼".into(),
+        Answer::Solved(answer) => answer.clone(),
+        Answer::Unsolved => "✖".into(),
+        Answer::Failed(error) => format!("✖ {}", error.lines().next().unwrap_or_default()),
+    }
+}
+
+/// Print the variants of a part side by side, relative to the primary variant.
+pub fn print_comparison(part: u8, results: &[VariantResult]) {
+    let Some(primary) = results.first() else {
+        return;
+    };
+
+    let verdict = if disagreement(results).is_some() {
+        "✖ answers differ"
+    } else {
+        "✔ answers agree"
+    };
+
+    let width = results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+
+    println!("{ANSI_BOLD}Part {part} variants: {verdict}{ANSI_RESET}");
+
+    for result in results {
+        let speed = if result.name == primary.name {
+            "-".into()
+        } else {
+            relative_speed(primary.duration, result.duration)
+        };
+
+        println!(
+            "  {:<width$} {:>12} {:>14}  {}",
+            result.name,
+            format!("{:.1?}", result.duration),
+            speed,
+            format_answer(&result.answer)
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{describe_disagreement, disagreement, relative_speed, VariantResult};
+    use crate::template::runner::Answer;
+
+    fn result(name: &str, answer: Answer, millis: u64) -> VariantResult {
+        VariantResult {
+            name: name.into(),
+            answer,
+            duration: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn accepts_agreeing_variants() {
+        let results = [
+            result("naive", Answer::Solved("42".into()), 100),
+            result("fast", Answer::Solved("42".into()), 10),
+        ];
+        assert_eq!(disagreement(&results), None);
+    }
+
+    #[test]
+    fn reports_the_first_disagreeing_variant() {
+        let results = [
+            result("naive", Answer::Solved("42".into()), 100),
+            result("fast", Answer::Solved("42".into()), 10),
+            result("wrong", Answer::Solved("41".into()), 10),
+            result("failing", Answer::Failed("oops".into()), 10),
+        ];
+        assert_eq!(
+            disagreement(&results).map(|r| r.name.as_str()),
+            Some("wrong")
+        );
+    }
+
+    #[test]
+    fn treats_failed_variants_as_disagreeing() {
+        let results = [
+            result("naive", Answer::Solved("42".into()), 100),
+            result("fast", Answer::Unsolved, 10),
+        ];
+        assert_eq!(
+            disagreement(&results).map(|r| r.name.as_str()),
+            Some("fast")
+        );
+    }
+
+    #[test]
+    fn describes_the_disagreement() {
+        let agreeing = [
+            result("naive", Answer::Solved("42".into()), 100),
+            result("fast", Answer::Solved("42".into()), 10),
+        ];
+        assert_eq!(describe_disagreement(&agreeing), None);
+
+        let disagreeing = [
+            result("naive", Answer::Solved("42".into()), 100),
+            result("fast", Answer::Unsolved, 10),
+        ];
+        assert_eq!(
+            describe_disagreement(&disagreeing).as_deref(),
+            Some("variant `fast` answered ✖ instead of 42")
+        );
+    }
+
+    #[test]
+    fn formats_relative_speed() {
+        let ms = Duration::from_millis;
+        assert_eq!(relative_speed(ms(100), ms(8)), "12.50x faster");
+        assert_eq!(relative_speed(ms(10), ms(25)), "2.50x slower");
+        assert_eq!(relative_speed(ms(10), ms(10)), "1.00x faster");
+    }
+}
@@ -0,0 +1,177 @@
+/// Module that checks the answers of solutions against the answers that were accepted by the server.
+use crate::template::answers::Answers;
+use crate::template::protocol::PartResult;
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// The result of verifying a single part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The part produced the accepted answer.
+    Pass,
+    /// The part produced a different answer, or none at all.
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// There is no accepted answer for the part.
+    Missing,
+}
+
+/// The verdicts of both parts of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayVerdict {
+    pub day: Day,
+    pub part_1: Verdict,
+    pub part_2: Verdict,
+}
+
+impl DayVerdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.part_1, Verdict::Fail { .. }) || matches!(self.part_2, Verdict::Fail { .. })
+    }
+}
+
+/// Compare the results of a day against its accepted answers.
+pub fn verify_day(answers: &Answers, day: Day, results: &[PartResult]) -> DayVerdict {
+    let verdict = |part: u8| {
+        let Some(expected) = answers.accepted(day, part) else {
+            return Verdict::Missing;
+        };
+
+        let actual = results
+            .iter()
+            .find(|r| r.part == part)
+            .and_then(|r| r.answer.clone());
+
+        if actual.as_deref().map(str::trim) == Some(expected.trim()) {
+            Verdict::Pass
+        } else {
+            Verdict::Fail {
+                expected: expected.to_string(),
+                actual,
+            }
+        }
+    };
+
+    DayVerdict {
+        day,
+        part_1: verdict(1),
+        part_2: verdict(2),
+    }
+}
+
+fn format_verdict(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Pass => "✔ pass",
+        Verdict::Fail { .. } => "✖ fail",
+        Verdict::Missing => "- missing",
+    }
+}
+
+/// Print a pass / fail / missing matrix, followed by the details of every failed part.
+pub fn print_matrix(verdicts: &[DayVerdict]) {
+    println!(
+        "{ANSI_BOLD}{:<5} {:<10} {:<10}{ANSI_RESET}",
+        "Day", "Part 1", "Part 2"
+    );
+
+    for verdict in verdicts {
+        let line = format!(
+            "{:<5} {:<10} {:<10}",
+            verdict.day.to_string(),
+            format_verdict(&verdict.part_1),
+            format_verdict(&verdict.part_2)
+        );
+
+        if verdict.is_failure() {
+            println!("{ANSI_BOLD}{line}{ANSI_RESET}");
+        } else {
+            println!("{line}");
+        }
+    }
+
+    for verdict in verdicts {
+        for (part, part_verdict) in [(1, &verdict.part_1), (2, &verdict.part_2)] {
+            if let Verdict::Fail { expected, actual } = part_verdict {
+                println!(
+                    "\nDay {} part {part}: expected {expected}, got {}",
+                    verdict.day,
+                    actual.as_deref().unwrap_or("no answer")
+                );
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{verify_day, Verdict};
+    use crate::{
+        day,
+        template::{answers::Answers, aoc_client::SubmissionOutcome, protocol::PartResult},
+    };
+
+    fn result(part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            part,
+            answer: answer.map(Into::into),
+            error: None,
+            duration: Duration::from_nanos(1),
+            samples: 1,
+            stats: None,
+            memory: None,
+        }
+    }
+
+    fn answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "41", SubmissionOutcome::Incorrect(None));
+        answers.record(day!(1), 1, "42", SubmissionOutcome::Correct);
+        answers.record(day!(1), 2, "7", SubmissionOutcome::Correct);
+        answers
+    }
+
+    #[test]
+    fn passes_matching_answers() {
+        let verdict = verify_day(
+            &answers(),
+            day!(1),
+            &[result(1, Some("42")), result(2, Some("7"))],
+        );
+        assert_eq!(verdict.part_1, Verdict::Pass);
+        assert_eq!(verdict.part_2, Verdict::Pass);
+        assert_eq!(verdict.is_failure(), false);
+    }
+
+    #[test]
+    fn fails_mismatching_and_absent_answers() {
+        let verdict = verify_day(&answers(), day!(1), &[result(1, Some("41"))]);
+        assert_eq!(
+            verdict.part_1,
+            Verdict::Fail {
+                expected: "42".into(),
+                actual: Some("41".into())
+            }
+        );
+        assert_eq!(
+            verdict.part_2,
+            Verdict::Fail {
+                expected: "7".into(),
+                actual: None
+            }
+        );
+        assert_eq!(verdict.is_failure(), true);
+    }
+
+    #[test]
+    fn reports_parts_without_accepted_answer_as_missing() {
+        let verdict = verify_day(&answers(), day!(2), &[result(1, Some("1"))]);
+        assert_eq!(verdict.part_1, Verdict::Missing);
+        assert_eq!(verdict.part_2, Verdict::Missing);
+        assert_eq!(verdict.is_failure(), false);
+    }
+}
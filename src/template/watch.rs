@@ -0,0 +1,125 @@
+/// Module that detects changes to the files of a day by polling their modification times.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::template::input::InputSource;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{Day, Puzzle};
+
+/// Modification times of the files of a day, by path. Files that do not exist are absent.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl Snapshot {
+    /// Take a snapshot of a day's solution, its puzzle input and its examples.
+    /// Examples are discovered on every snapshot, so that newly added examples are picked up.
+    pub fn take(puzzle: Puzzle) -> Self {
+        let mut paths = vec![PathBuf::from(get_path_for_bin(puzzle))];
+        paths.extend(InputSource::Puzzle.path(puzzle));
+
+        if let Ok(entries) = fs::read_dir(puzzle.data_dir().join("examples")) {
+            paths.extend(
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|e| is_example_of(&e.file_name().to_string_lossy(), puzzle.day))
+                    .map(|e| e.path()),
+            );
+        }
+
+        Self {
+            files: paths
+                .into_iter()
+                .filter_map(|path| {
+                    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                    Some((path, modified))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.files.keys()
+    }
+
+    /// The files that were added, removed or modified since `previous`.
+    pub fn changes(&self, previous: &Snapshot) -> Vec<PathBuf> {
+        let modified = self
+            .files
+            .iter()
+            .filter(|(path, time)| previous.files.get(*path) != Some(*time))
+            .map(|(path, _)| path.clone());
+
+        let removed = previous
+            .files
+            .keys()
+            .filter(|path| !self.files.contains_key(*path))
+            .cloned();
+
+        modified.chain(removed).collect()
+    }
+}
+
+/// Whether a file of the examples folder belongs to a day, i.e. `01.txt`, `01-2.txt` or its manifest `01.json`.
+fn is_example_of(file_name: &str, day: Day) -> bool {
+    let Some(rest) = file_name.strip_prefix(&day.to_string()) else {
+        return false;
+    };
+
+    rest == ".json" || rest.starts_with(['.', '-']) && rest.ends_with(".txt")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    use super::{is_example_of, Snapshot};
+    use crate::day;
+
+    fn snapshot(files: &[(&str, u64)]) -> Snapshot {
+        Snapshot {
+            files: files
+                .iter()
+                .map(|(path, secs)| {
+                    (
+                        PathBuf::from(path),
+                        SystemTime::UNIX_EPOCH + Duration::from_secs(*secs),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn matches_examples_of_a_day() {
+        assert!(is_example_of("01.txt", day!(1)));
+        assert!(is_example_of("01-2.txt", day!(1)));
+        assert!(is_example_of("01.json", day!(1)));
+        assert!(!is_example_of("11.txt", day!(1)));
+        assert!(!is_example_of("01.txt.swp", day!(1)));
+        assert!(!is_example_of("010.txt", day!(1)));
+    }
+
+    #[test]
+    fn detects_changes() {
+        let previous = snapshot(&[("src/bin/01.rs", 1), ("data/examples/01.txt", 1)]);
+
+        assert_eq!(previous.changes(&previous), Vec::<PathBuf>::new());
+
+        let current = snapshot(&[("src/bin/01.rs", 2), ("data/inputs/01.txt", 1)]);
+        assert_eq!(
+            current.changes(&previous),
+            vec![
+                PathBuf::from("data/inputs/01.txt"),
+                PathBuf::from("src/bin/01.rs"),
+                PathBuf::from("data/examples/01.txt"),
+            ]
+        );
+    }
+}
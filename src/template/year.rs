@@ -0,0 +1,255 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::MAX_DAYS;
+
+/// The first year of advent.
+const FIRST_YEAR: u16 = 2015;
+
+/// The number of days of the events, used if `AOC_EVENT_DAYS` is not configured.
+const DEFAULT_EVENT_DAYS: &str = "2015=25,2025=12";
+
+/// The number of days of the events, configured at build time through `AOC_EVENT_DAYS`, e.g. `2015=25,2025=12`.
+/// Every entry sets the number of days from its year on, until the year of the next entry.
+const EVENT_DAYS: &str = match option_env!("AOC_EVENT_DAYS") {
+    Some(config) => config,
+    None => DEFAULT_EVENT_DAYS,
+};
+
+/// The default year at build time, see [`Year::from_env`].
+const BUILD_YEAR: Option<u16> = match option_env!("AOC_YEAR") {
+    Some(year) => parse_number(year.as_bytes(), 0).0,
+    None => None,
+};
+
+/// A valid year of advent (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year of advent,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The number of days of this year's event as configured by `AOC_EVENT_DAYS`,
+    /// by default 25 up to 2024 and 12 since 2025.
+    pub const fn day_count(self) -> u8 {
+        event_day_count(EVENT_DAYS, self.0)
+    }
+
+    /// The default year, configured through `AOC_YEAR`.
+    /// Files of the default year use the unscoped layout, e.g. `data/inputs/01.txt` and `src/bin/01.rs`.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.trim().parse().ok()
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+/// Returns whether `year` refers to the default year, either implicitly or by being equal to `AOC_YEAR`.
+pub fn is_default_year(year: Option<Year>) -> bool {
+    year.is_none() || year == Year::from_env()
+}
+
+/// The number of days of a year's event, resolving the default year from `AOC_YEAR`.
+/// Falls back to [`MAX_DAYS`] if no year is configured.
+pub fn day_count(year: Option<Year>) -> u8 {
+    year.or_else(Year::from_env)
+        .map_or(MAX_DAYS, Year::day_count)
+}
+
+// Not part of the public API, used by `solution!` to check the day of a solution at build time.
+#[doc(hidden)]
+pub const fn __build_day_count(year: Option<Year>) -> u8 {
+    match (year, BUILD_YEAR) {
+        (Some(year), _) => year.day_count(),
+        (None, Some(year)) => event_day_count(EVENT_DAYS, year),
+        (None, None) => MAX_DAYS,
+    }
+}
+
+/// Looks up the number of days of a year's event in a configuration like `2015=25,2025=12`.
+/// Years before the first entry have [`MAX_DAYS`]. Panics if the configuration is invalid,
+/// which fails the build if it's evaluated in a const context.
+const fn event_day_count(config: &str, year: u16) -> u8 {
+    const INVALID: &str =
+        "invalid `AOC_EVENT_DAYS`, expecting entries of `<year>=<days>` separated by commas, \
+        with days between 1 and 25, e.g. `2015=25,2025=12`";
+
+    let bytes = config.as_bytes();
+    let mut days = MAX_DAYS;
+    let mut from_year = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let (Some(entry_year), next) = parse_number(bytes, i) else {
+            panic!("{}", INVALID);
+        };
+        if next >= bytes.len() || bytes[next] != b'=' {
+            panic!("{}", INVALID);
+        }
+        let (Some(entry_days), next) = parse_number(bytes, next + 1) else {
+            panic!("{}", INVALID);
+        };
+        if entry_days == 0 || entry_days > MAX_DAYS as u16 {
+            panic!("{}", INVALID);
+        }
+
+        // entries may be listed in any order, the latest one up to the year applies.
+        if entry_year <= year && entry_year >= from_year {
+            from_year = entry_year;
+            days = entry_days as u8;
+        }
+
+        i = next;
+        if i < bytes.len() {
+            if bytes[i] != b',' {
+                panic!("{}", INVALID);
+            }
+            i += 1;
+        }
+    }
+
+    days
+}
+
+/// Parses a number at `start`, surrounded by optional spaces. Returns the number and the index after it.
+const fn parse_number(bytes: &[u8], start: usize) -> (Option<u16>, usize) {
+    let mut i = start;
+    while i < bytes.len() && bytes[i] == b' ' {
+        i += 1;
+    }
+
+    let mut value: Option<u16> = None;
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+        let digit = (bytes[i] - b'0') as u16;
+        value = match value {
+            None => Some(digit),
+            Some(v) => match v.checked_mul(10) {
+                Some(v) => v.checked_add(digit),
+                None => None,
+            },
+        };
+        if value.is_none() {
+            return (None, i);
+        }
+        i += 1;
+    }
+
+    while i < bytes.len() && bytes[i] == b' ' {
+        i += 1;
+    }
+
+    (value, i)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year of advent, i.e. {FIRST_YEAR} or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a year of advent, i.e. 2015 or later"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{event_day_count, Year};
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().ok(), Some(Year(2023)));
+        assert_eq!("2014".parse::<Year>().ok(), None);
+        assert_eq!("23".parse::<Year>().ok(), None);
+        assert_eq!("abcd".parse::<Year>().ok(), None);
+    }
+
+    #[test]
+    fn counts_days_per_event() {
+        assert_eq!(Year(2015).day_count(), 25);
+        assert_eq!(Year(2024).day_count(), 25);
+        assert_eq!(Year(2025).day_count(), 12);
+    }
+
+    #[test]
+    fn reads_configured_event_days() {
+        let config = "2015=25, 2025=12, 2020=20";
+        assert_eq!(event_day_count(config, 2015), 25);
+        assert_eq!(event_day_count(config, 2019), 25);
+        assert_eq!(event_day_count(config, 2021), 20);
+        assert_eq!(event_day_count(config, 2026), 12);
+        assert_eq!(event_day_count("2030=10", 2029), 25);
+        assert_eq!(event_day_count("", 2024), 25);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid `AOC_EVENT_DAYS`")]
+    fn rejects_invalid_event_days() {
+        event_day_count("2025=30", 2025);
+    }
+}